@@ -2,15 +2,21 @@ use std::fmt::Display;
 use std::sync::LazyLock;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use crate::client::rules::Rule;
-use crate::java::JAVA_SEPARATOR;
+use crate::client::rules::{Rule, RuleContext};
 use crate::minecraft::arguments::DataMap;
-use crate::minecraft::configuration::MinecraftConfiguration as Configuration;
+use crate::minecraft::configuration::{MinecraftConfiguration as Configuration, PlaceholderMode};
 
 use super::CollectArguments;
 
 static ARGUMENT_FIND_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$\{([^}]+)\}").unwrap());
 
+/// JVM arguments that the official launcher uses for versions with ``minecraftArguments``
+const LEGACY_JVM_ARGUMENTS: [&str; 3] = [
+  "-Djava.library.path=${natives_directory}",
+  "-cp",
+  "${classpath}"
+];
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Arguments {
   /// Contains JVM arguments, such as information about memory allocation, garbage collector selection, or environmental variables.
@@ -26,47 +32,74 @@ pub enum ArgumentValue {
   Array(Vec<String>)
 }
 
+impl ArgumentValue {
+  /// Every string is a separate argument of the process, they are never split or joined.
+  pub fn tokens(&self) -> &[String] {
+    match self {
+      ArgumentValue::String(s) => std::slice::from_ref(s),
+      ArgumentValue::Array(a) => a
+    }
+  }
+}
+
 impl Display for ArgumentValue {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    let str = match self {
-      ArgumentValue::String(s) => s.to_owned(), // cloning :(
-      ArgumentValue::Array(a) => a.join(JAVA_SEPARATOR),
-    };
-    write!(f, "{}", str)
+    write!(f, "{}", self.tokens().join(" "))
   }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "ArgumentRepr")]
 pub struct Argument {
   value: ArgumentValue,
   rules: Option<Vec<Rule>>
 }
 
-impl Display for Argument {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    if let Some(rules) = &self.rules {
-      for rule in rules {
-        if !rule.is_followed() {
-          return write!(f, "{}", String::new())
-        }
-      }
+/// Arguments can be written both as plain strings and as objects with rules
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ArgumentRepr {
+  Plain(String),
+  Ruled {
+    value: ArgumentValue,
+    rules: Option<Vec<Rule>>
+  }
+}
+
+impl From<ArgumentRepr> for Argument {
+  fn from(repr: ArgumentRepr) -> Self {
+    match repr {
+      ArgumentRepr::Plain(value) => Self { value: ArgumentValue::String(value), rules: None },
+      ArgumentRepr::Ruled { value, rules } => Self { value, rules }
     }
+  }
+}
+
+impl From<&str> for Argument {
+  fn from(value: &str) -> Self {
+    Self { value: ArgumentValue::String(value.to_string()), rules: None }
+  }
+}
 
+impl Display for Argument {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     write!(f, "{}", self.value)
   }
 }
 
+impl Argument {
+  /// Are the rules of the argument followed in ``context``?
+  pub fn is_allowed(&self, context: &RuleContext) -> bool {
+    context.allows(self.rules.as_deref().unwrap_or_default())
+  }
+}
+
 impl CollectArguments for Vec<Argument> {
-  fn collect(&self) -> Vec<String> {
+  fn collect(&self, context: &RuleContext) -> Vec<String> {
     let mut result = Vec::new();
 
-    for arg in self {
-      for part in arg.to_string().split(';') {
-        let trimmed = part.trim();
-        if !trimmed.is_empty() {
-          result.push(trimmed.to_string());
-        }
-      }
+    for arg in self.iter().filter(|arg| arg.is_allowed(context)) {
+      result.extend(arg.value.tokens().iter().cloned());
     }
 
     result
@@ -81,12 +114,16 @@ fn jvm_setup(config: &Configuration, jvm: &mut Vec<Argument>) {
 }
 
 fn game_setup(config: &Configuration, game: &mut Vec<Argument>) {
-  if config.window.width.is_some() {
-    game.push(Argument { value: ArgumentValue::String("--width; ${width}".to_string()), rules: None });
+  // modern versions pass ``--width ${resolution_width}`` themselves
+  let has_resolution = game.iter()
+    .any(|arg| arg.value.tokens().iter().any(|token| token == "--width"));
+
+  if config.window.width.is_some() && !has_resolution {
+    game.push(Argument { value: ArgumentValue::Array(vec!["--width".to_string(), "${width}".to_string()]), rules: None });
   }
 
-  if config.window.height.is_some() {
-    game.push(Argument { value: ArgumentValue::String("--height; ${height}".to_string()), rules: None });
+  if config.window.height.is_some() && !has_resolution {
+    game.push(Argument { value: ArgumentValue::Array(vec!["--height".to_string(), "${height}".to_string()]), rules: None });
   }
 
   if config.window.fullscreen.is_some() {
@@ -95,33 +132,95 @@ fn game_setup(config: &Configuration, game: &mut Vec<Argument>) {
 }
 
 impl Arguments {
+  /// Builds arguments from the legacy (pre-1.13) ``minecraftArguments`` string.
+  ///
+  /// The string becomes the game arguments, and JVM arguments are the same that the official launcher passes to legacy versions.
+  pub fn from_legacy(minecraft_arguments: &str) -> Self {
+    Self {
+      jvm: LEGACY_JVM_ARGUMENTS.into_iter().map(Argument::from).collect(),
+      game: minecraft_arguments.split_whitespace().map(Argument::from).collect()
+    }
+  }
+
+  /// Adds JVM argument in front of others.
+  pub fn add_jvm(&mut self, argument: &str) {
+    self.jvm.insert(0, Argument::from(argument));
+  }
+
   /// Combines the arguments from ``jvm`` and ``game``,
   /// turning them into a single array of strings that can be used as arguments to start a process.
   pub fn collect(&mut self, config: &Configuration, data_map: DataMap<'_>) -> std::io::Result<Vec<String>> {
+    let (mut result, game) = self.collect_parts(config, &data_map)?;
+
+    // vanilla files don't mention main class in arguments, launcher puts it between jvm and game arguments
+    result.push(data_map.get("main_class").cloned().unwrap_or_default());
+    result.extend(game);
+
+    Ok(result)
+  }
+
+  /// Same as [`Arguments::collect`], but JVM and game arguments are returned separately, without main class.\
+  /// Arguments after ``${main_class}`` in ``jvm`` (if it's there) are game arguments.
+  pub fn collect_parts(&mut self, config: &Configuration, data_map: &DataMap<'_>) -> std::io::Result<(Vec<String>, Vec<String>)> {
     jvm_setup(config, &mut self.jvm);
     game_setup(config, &mut self.game);
 
-    let mut result = self.jvm.collect();
-    result.extend(self.game.collect());
+    let context = RuleContext::from(config);
+    let mut jvm = self.jvm.collect(&context);
+    let mut game = self.game.collect(&context);
+
+    if let Some(index) = jvm.iter().position(|arg| arg == "${main_class}") {
+      let rest = jvm.split_off(index);
+      game.splice(0..0, rest.into_iter().skip(1));
+    }
+
+    let mut unknown = Vec::new();
+    let jvm = substitute(jvm, data_map, &config.placeholders, &mut unknown);
+    let game = substitute(game, data_map, &config.placeholders, &mut unknown);
+
+    if config.placeholders == PlaceholderMode::Strict && !unknown.is_empty() {
+      return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("Unknown placeholders in arguments: {}", unknown.join(", "))));
+    }
+
+    Ok((jvm, game))
+  }
+}
+
+/// Replaces ``${name}`` placeholders with values of ``data_map``, names of unknown ones are added to ``unknown``
+fn substitute(arguments: Vec<String>, data_map: &DataMap<'_>, mode: &PlaceholderMode, unknown: &mut Vec<String>) -> Vec<String> {
+  let pattern = &ARGUMENT_FIND_PATTERN; // regex: \$\{([^}]+)\}
+  let mut final_result: Vec<String> = Vec::new();
 
-    let pattern = &ARGUMENT_FIND_PATTERN; // regex: \$\{([^}]+)\}
-    let mut final_result = Vec::new();
+  for arg in arguments.iter() {
+    let mut new_arg = arg.clone();
+    let mut is_resolved = true;
 
-    for arg in result.iter() {
-      let mut new_arg = arg.clone();
+    for cap in pattern.captures_iter(arg) {
+      let key = &cap[1];
 
-      for cap in pattern.captures_iter(arg) {
-        let key = &cap[1];
+      match data_map.get(key) {
+        Some(value) => new_arg = new_arg.replace(&cap[0], value),
+        None => {
+          is_resolved = false;
 
-        if let Some(value) = data_map.get(key) {
-          new_arg = new_arg.replace(&cap[0], value);
+          if !unknown.iter().any(|name| name == key) {
+            unknown.push(key.to_string());
+          }
         }
-        // TODO @ ignore not changed strings
+      }
+    }
+
+    if !is_resolved && *mode == PlaceholderMode::Lenient {
+      // value of a flag is dropped with the flag: "--clientId ${clientid}"
+      if !arg.starts_with('-') && final_result.last().is_some_and(|last| last.starts_with('-')) {
+        final_result.pop();
       }
 
-      final_result.push(new_arg);
+      continue;
     }
 
-    Ok(final_result)
+    final_result.push(new_arg);
   }
-}
\ No newline at end of file
+
+  final_result
+}
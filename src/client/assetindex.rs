@@ -1,4 +1,9 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Error, ErrorKind, Read};
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
+use crate::client::maven::artifact_path;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AssetIndex {
@@ -15,4 +20,105 @@ pub struct AssetIndex {
   pub known: Option<bool>,
   /// The URL that the game should visit to download the assets.
   pub url: String
-}
\ No newline at end of file
+}
+
+impl AssetIndex {
+  /// Path to the index file: ``assets/indexes/<id>.json``.
+  pub fn get_path(&self, assets_dir: &Path) -> PathBuf {
+    assets_dir.join("indexes").join(format!("{}.json", self.id))
+  }
+
+  /// Reads the index file from ``assets_dir``.
+  pub fn read(&self, assets_dir: &Path) -> io::Result<AssetIndexFile> {
+    AssetIndexFile::new(self.get_path(assets_dir))
+  }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AssetObject {
+  /// The SHA1 of the object, also its name in ``assets/objects``.
+  pub hash: String,
+  /// The size of the object in bytes.
+  pub size: usize
+}
+
+impl AssetObject {
+  /// Path to the object: ``assets/objects/<first 2 chars of hash>/<hash>``.
+  pub fn get_path(&self, assets_dir: &Path) -> PathBuf {
+    let prefix = self.hash.get(..2).unwrap_or_default();
+
+    assets_dir.join("objects")
+      .join(prefix)
+      .join(&self.hash)
+  }
+}
+
+/// Implementation of ``assets/indexes/<id>.json`` files structure
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AssetIndexFile {
+  /// Every asset of the index, by its name (e.g. ``minecraft/sounds/ambient/cave/cave1.ogg``).
+  pub objects: HashMap<String, AssetObject>,
+  /// Assets are expected in ``assets/virtual/legacy`` (1.6 - 1.7.2).
+  #[serde(default)]
+  pub r#virtual: bool,
+  /// Assets are expected in ``<game_dir>/resources`` (1.5.2 and below).
+  #[serde(default)]
+  pub map_to_resources: bool
+}
+
+impl AssetIndexFile {
+  pub fn new(path: PathBuf) -> io::Result<Self> {
+    if !path.is_file() {
+      return Err(Error::new(ErrorKind::NotFound, format!("Asset index {} is not a file", path.display())));
+    }
+
+    let mut content = String::new();
+    File::open(path)?.read_to_string(&mut content)?;
+
+    Ok(serde_json::from_str(&content)?)
+  }
+
+  /// Folder where the game looks for assets (``${game_assets}``).
+  pub fn get_game_assets_dir(&self, assets_dir: &Path, game_dir: &Path) -> PathBuf {
+    if self.map_to_resources {
+      return game_dir.join("resources");
+    }
+
+    if self.r#virtual {
+      return assets_dir.join("virtual").join("legacy");
+    }
+
+    assets_dir.to_path_buf()
+  }
+
+  /// Copies objects to their legacy locations by name, if the index is ``virtual`` or ``map_to_resources``.
+  ///
+  /// Files that already exist with the right size are not copied again.
+  pub fn reconstruct(&self, assets_dir: &Path, game_dir: &Path) -> io::Result<()> {
+    if !self.r#virtual && !self.map_to_resources {
+      return Ok(());
+    }
+
+    let target_dir = self.get_game_assets_dir(assets_dir, game_dir);
+
+    for (name, object) in &self.objects {
+      // names come from remote index, they can't leave the target folder
+      let target = target_dir.join(artifact_path(name)?);
+
+      let is_actual = fs::metadata(&target)
+        .is_ok_and(|metadata| metadata.len() == object.size as u64);
+
+      if is_actual {
+        continue;
+      }
+
+      if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+      }
+
+      fs::copy(object.get_path(assets_dir), target)?;
+    }
+
+    Ok(())
+  }
+}
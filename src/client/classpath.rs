@@ -1,40 +1,43 @@
+use std::io;
+use std::path::PathBuf;
+use crate::java::JAVA_SEPARATOR;
 use crate::minecraft::configuration::MinecraftConfiguration as Configuration;
+use super::rules::RuleContext;
 use super::ClientFile;
 
-const JAVA_CLASSPATH_SEPARATOR: &str = if cfg!(target_os = "windows") { ";" } else { ":" };
-
 pub struct ClassPathCollector;
 
 impl ClassPathCollector {
-  pub fn collect(
+  /// Jars of allowed libraries and the client jar, in the order of the class path.
+  pub fn collect_entries(
     config: &Configuration,
     client_file: &ClientFile
-  ) -> String {
-    let mut result: Vec<String> = Vec::new();
-    let libraries_dir = config.client.path.join("libraries");
+  ) -> io::Result<Vec<PathBuf>> {
+    let mut result: Vec<PathBuf> = Vec::new();
+    let libraries_dir = config.client.get_libraries_dir();
 
-    for lib in &client_file.libraries {
-      let mut is_followed = true;
-      if let Some(rules) = &lib.rules {
-        for rule in rules {
-          if !rule.is_followed() {
-            is_followed = false;
-            break
-          }
-        }
-      }
+    let context = RuleContext::from(config);
 
-      if !is_followed {
+    for lib in &client_file.libraries {
+      if !lib.is_allowed(&context) || lib.is_natives_only() {
         continue;
       }
 
-      let path = lib.to_path();
+      let path = lib.to_path()?;
 
-      result.push(libraries_dir.join(&path).to_str().unwrap().to_string());
+      result.push(libraries_dir.join(&path));
     }
 
-    result.push(config.client.path.join("versions").join(&config.client.version).join("client.jar").to_str().unwrap().to_string());
+    result.push(config.client.get_client_jar(client_file));
+
+    Ok(result)
+  }
 
-    result.join(JAVA_CLASSPATH_SEPARATOR)
+  /// Joins entries with the separator of the OS (``:`` or ``;``)
+  pub fn join(entries: &[PathBuf]) -> String {
+    entries.iter()
+      .map(|entry| entry.to_str().unwrap().to_string())
+      .collect::<Vec<String>>()
+      .join(JAVA_SEPARATOR)
   }
-}
\ No newline at end of file
+}
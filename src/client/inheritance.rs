@@ -0,0 +1,134 @@
+use std::fs::File;
+use std::io::{self, Error, ErrorKind, Read};
+use std::path::Path;
+use serde_json::{Map, Value};
+use super::maven::MavenCoordinate;
+use super::{find_version_file, ClientFile};
+
+/// Loads client files, that point to their parent via ``inheritsFrom`` (Forge, Fabric, Quilt, OptiFine, etc.)
+/// and merges the whole chain into a single [`ClientFile`].
+pub struct ClientResolver;
+
+impl ClientResolver {
+  /// Resolves version ``version`` located in ``versions_dir`` (``.minecraft/versions``).
+  pub fn resolve(versions_dir: &Path, version: &str) -> io::Result<ClientFile> {
+    let mut visited = vec![version.to_string()];
+    let mut merged = read_version(versions_dir, version)?;
+    set_jar(&mut merged, versions_dir, version);
+
+    while let Some(parent) = take_parent(&mut merged) {
+      if visited.contains(&parent) {
+        return Err(Error::new(ErrorKind::InvalidData, format!("Version {} inherits from itself", parent)));
+      }
+
+      let mut parent_file = read_version(versions_dir, &parent)?;
+      set_jar(&mut parent_file, versions_dir, &parent);
+
+      merged = merge(parent_file, merged);
+      visited.push(parent);
+    }
+
+    serde_json::from_value(Value::Object(merged))
+      .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+  }
+}
+
+fn read_version(versions_dir: &Path, version: &str) -> io::Result<Map<String, Value>> {
+  let path = find_version_file(versions_dir, version, "json");
+
+  if !path.is_file() {
+    return Err(Error::new(ErrorKind::NotFound, format!("Version file of {} is not a file", version)));
+  }
+
+  let mut content = String::new();
+  File::open(path)?.read_to_string(&mut content)?;
+
+  match serde_json::from_str(&content)? {
+    Value::Object(map) => Ok(map),
+    _ => Err(Error::new(ErrorKind::InvalidData, format!("Version file of {} is not an object", version)))
+  }
+}
+
+fn take_parent(file: &mut Map<String, Value>) -> Option<String> {
+  match file.remove("inheritsFrom")? {
+    Value::String(parent) => Some(parent),
+    _ => None
+  }
+}
+
+/// Points ``jar`` to the version itself if it has own jar file, so children without jar will take it
+fn set_jar(file: &mut Map<String, Value>, versions_dir: &Path, version: &str) {
+  if !file.contains_key("jar") && find_version_file(versions_dir, version, "jar").is_file() {
+    file.insert(String::from("jar"), Value::String(version.to_string()));
+  }
+}
+
+/// ``group:artifact[:classifier]`` of the library, version is omitted so different versions replace each other
+fn library_key(library: &Value) -> Option<String> {
+  let coordinate = library.get("name")?
+    .as_str()?
+    .parse::<MavenCoordinate>()
+    .ok()?;
+
+  Some(coordinate.key())
+}
+
+fn merge_libraries(parent: Value, child: Value) -> Value {
+  let mut result = match child {
+    Value::Array(libraries) => libraries,
+    _ => Vec::new()
+  };
+
+  // parent may list the same library several times with different rules (e.g. LWJGL for macOS)
+  let keys = result.iter()
+    .filter_map(library_key)
+    .collect::<Vec<String>>();
+
+  if let Value::Array(libraries) = parent {
+    result.extend(libraries.into_iter()
+      .filter(|library| !library_key(library).is_some_and(|key| keys.contains(&key))));
+  }
+
+  Value::Array(result)
+}
+
+fn merge_arguments(parent: Value, child: Value) -> Value {
+  let (mut parent, child) = match (parent, child) {
+    (Value::Object(parent), Value::Object(child)) => (parent, child),
+    (_, child) => return child
+  };
+
+  for (kind, arguments) in child {
+    let Value::Array(arguments) = arguments else {
+      continue;
+    };
+
+    match parent.get_mut(&kind) {
+      Some(Value::Array(parent_arguments)) => parent_arguments.extend(arguments),
+      _ => { parent.insert(kind, Value::Array(arguments)); }
+    }
+  }
+
+  Value::Object(parent)
+}
+
+/// Merges ``child`` into ``parent``.
+///
+/// Libraries are taken from the child first and deduplicated, arguments are appended,
+/// everything else (``mainClass``, ``assetIndex``, ``javaVersion``, etc.) is overridden by the child.
+fn merge(mut parent: Map<String, Value>, child: Map<String, Value>) -> Map<String, Value> {
+  for (key, value) in child {
+    let merged = match key.as_str() {
+      "libraries" => merge_libraries(parent.remove(&key).unwrap_or(Value::Null), value),
+      "arguments" => match parent.remove(&key) {
+        Some(parent_value) => merge_arguments(parent_value, value),
+        None => value
+      },
+      _ => value
+    };
+
+    parent.insert(key, merged);
+  }
+
+  parent
+}
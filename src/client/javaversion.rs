@@ -1,8 +1,33 @@
 use serde::{Deserialize, Serialize};
+use super::timestamp::Timestamp;
+
+/// Release times of the first versions, that required newer Java: 21w19a (16), 1.18-pre2 (17) and 24w14a (21)
+const JAVA_REQUIREMENTS: [(i64, &str, usize); 3] = [
+  (1712102400, "java-runtime-delta", 21),
+  (1637020800, "java-runtime-gamma", 17),
+  (1620777600, "java-runtime-alpha", 16)
+];
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ClientJavaVersion {
-  component: String,
+  /// Mojang's Java runtime, that the version is made for (e.g. ``java-runtime-gamma``), see [`crate::java::runtime`].
+  pub component: String,
+  /// Major version of Java (e.g. 8, 17, 21).
   #[serde(rename = "majorVersion")]
-  major_version: usize
-}
\ No newline at end of file
+  pub major_version: usize
+}
+
+impl ClientJavaVersion {
+  /// Java required by versions released at ``release_time``, is used for old client files without ``javaVersion``.
+  pub fn infer(release_time: Timestamp) -> Self {
+    let (component, major_version) = JAVA_REQUIREMENTS.iter()
+      .find(|(since, ..)| release_time.unix() >= *since)
+      .map(|(_, component, major_version)| (*component, *major_version))
+      .unwrap_or(("jre-legacy", 8));
+
+    Self {
+      component: component.to_string(),
+      major_version
+    }
+  }
+}
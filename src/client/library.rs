@@ -1,8 +1,9 @@
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::io;
+use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
-use crate::client::downloads::DownloadableObject;
-use crate::client::rules::Rule;
+use crate::client::maven::{artifact_path, MavenCoordinate};
+use crate::client::rules::{OsKind, Rule, RuleContext};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Natives {
@@ -15,7 +16,7 @@ pub struct Natives {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LibraryDownloads {
   pub artifact: Option<LibraryArtifact>,
-  pub classifiers: Option<HashMap<String, DownloadableObject>>
+  pub classifiers: Option<HashMap<String, LibraryArtifact>>
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -42,14 +43,74 @@ pub struct Library {
 }
 
 impl Library {
-  pub fn to_path(&self) -> PathBuf {
-    let parts: Vec<&str> = self.name.split(':').collect();
-    let subparts: Vec<&str> = parts[0].split('.').collect();
-    let joined_subparts = subparts.join(std::path::MAIN_SEPARATOR_STR);
-
-    Path::new(&joined_subparts)
-      .join(parts[1])
-      .join(parts[2])
-      .join(format!("{}-{}.jar", parts[1], parts[2]))
+  /// Parsed maven coordinate of the library.
+  pub fn coordinate(&self) -> io::Result<MavenCoordinate> {
+    self.name.parse()
+  }
+
+  /// Are the rules of the library followed in ``context``?
+  pub fn is_allowed(&self, context: &RuleContext) -> bool {
+    context.allows(self.rules.as_deref().unwrap_or_default())
+  }
+
+  /// Path of the library relative to ``libraries`` folder.
+  ///
+  /// ``downloads.artifact.path`` is used if present, otherwise path is derived from the maven coordinate.
+  pub fn to_path(&self) -> io::Result<PathBuf> {
+    let path = self.downloads.as_ref()
+      .and_then(|downloads| downloads.artifact.as_ref())
+      .and_then(|artifact| artifact.path.as_deref());
+
+    match path {
+      Some(path) => artifact_path(path),
+      None => Ok(self.coordinate()?.to_path())
+    }
+  }
+
+  /// Library contains only native jars (e.g. ``lwjgl-platform``), so it shouldn't be added to the classpath.
+  pub fn is_natives_only(&self) -> bool {
+    self.natives.is_some() && self.downloads.as_ref().and_then(|downloads| downloads.artifact.as_ref()).is_none()
+  }
+
+  /// Classifier of native jar for the OS and architecture of ``context`` (e.g. ``natives-windows-64``).
+  pub fn get_natives_classifier(&self, context: &RuleContext) -> Option<String> {
+    let natives = self.natives.as_ref()?;
+
+    let classifier = match context.os {
+      OsKind::Windows => natives.windows.as_ref(),
+      OsKind::MacOS => natives.osx.as_ref().or(natives.macos.as_ref()),
+      OsKind::Linux => natives.linux.as_ref()
+    }?;
+
+    let bits = match context.arch.as_str() {
+      "x86" => "32",
+      _ => "64"
+    };
+
+    Some(classifier.replace("${arch}", bits))
+  }
+
+  /// Path of native jar relative to ``libraries`` folder, if library has natives for the OS of ``context``.
+  ///
+  /// ``downloads.classifiers`` is used if present, otherwise path is derived from the maven coordinate.
+  pub fn get_natives_path(&self, context: &RuleContext) -> io::Result<Option<PathBuf>> {
+    let Some(classifier) = self.get_natives_classifier(context) else {
+      return Ok(None);
+    };
+
+    let path = self.downloads.as_ref()
+      .and_then(|downloads| downloads.classifiers.as_ref())
+      .and_then(|classifiers| classifiers.get(&classifier))
+      .and_then(|object| object.path.as_deref());
+
+    match path {
+      Some(path) => Ok(Some(artifact_path(path)?)),
+      None => {
+        let mut coordinate = self.coordinate()?;
+        coordinate.classifier = Some(classifier);
+
+        Ok(Some(coordinate.to_path()))
+      }
+    }
   }
 }
\ No newline at end of file
@@ -0,0 +1,48 @@
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LoggingFile {
+  /// The name of the config file (e.g. client-1.12.xml).
+  pub id: String,
+  /// The SHA1 of the config file.
+  pub sha1: String,
+  /// The size of the config file in bytes.
+  pub size: usize,
+  /// The URL where the config file is hosted.
+  pub url: String
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LoggingConfiguration {
+  /// JVM argument that adds the configuration (e.g. ``-Dlog4j.configurationFile=${path}``).
+  pub argument: String,
+  /// The log4j configuration file.
+  pub file: LoggingFile,
+  /// Type of the configuration (``log4j2-xml``).
+  pub r#type: String
+}
+
+impl LoggingConfiguration {
+  /// Path to the config file, it should be located in ``assets/log_configs``.
+  pub fn get_path(&self, assets_dir: &Path) -> PathBuf {
+    assets_dir.join("log_configs").join(&self.file.id)
+  }
+
+  /// Returns JVM argument with path to the config file, if it's present.
+  pub fn get_argument(&self, assets_dir: &Path) -> Option<String> {
+    let path = self.get_path(assets_dir);
+
+    if !path.is_file() {
+      return None;
+    }
+
+    Some(self.argument.replace("${path}", path.to_str()?))
+  }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Logging {
+  /// Logging configuration of the client.
+  pub client: Option<LoggingConfiguration>
+}
@@ -0,0 +1,118 @@
+use std::fmt::Display;
+use std::io::{self, Error, ErrorKind};
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+/// Maven coordinate in the form of ``groupId:artifactId:version[:classifier][@extension]``
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MavenCoordinate {
+  pub group: String,
+  pub artifact: String,
+  pub version: String,
+  /// Classifier of the artifact (e.g. ``natives-linux``).
+  pub classifier: Option<String>,
+  /// Extension of the artifact, ``jar`` by default.
+  pub extension: String
+}
+
+impl FromStr for MavenCoordinate {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let invalid = || Error::new(ErrorKind::InvalidData, format!("Invalid maven coordinate: {}", s));
+
+    let (coordinate, extension) = match s.split_once('@') {
+      Some((coordinate, extension)) => (coordinate, extension),
+      None => (s, "jar")
+    };
+
+    let parts = coordinate.split(':').collect::<Vec<&str>>();
+
+    if !(3..=4).contains(&parts.len()) || parts.iter().any(|part| part.is_empty()) || extension.is_empty() {
+      return Err(invalid());
+    }
+
+    // parts become folders and file name of the artifact
+    let is_safe = |part: &str| !part.contains(['/', '\\']) && part != "." && part != "..";
+
+    if !parts.iter().chain([&extension]).all(|part| is_safe(part)) || parts[0].split('.').any(str::is_empty) {
+      return Err(invalid());
+    }
+
+    Ok(Self {
+      group: parts[0].to_string(),
+      artifact: parts[1].to_string(),
+      version: parts[2].to_string(),
+      classifier: parts.get(3).map(|classifier| classifier.to_string()),
+      extension: extension.to_string()
+    })
+  }
+}
+
+impl Display for MavenCoordinate {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}:{}:{}", self.group, self.artifact, self.version)?;
+
+    if let Some(classifier) = &self.classifier {
+      write!(f, ":{}", classifier)?;
+    }
+
+    if self.extension != "jar" {
+      write!(f, "@{}", self.extension)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl MavenCoordinate {
+  /// ``groupId:artifactId[:classifier]``, identifies the library regardless of its version.
+  pub fn key(&self) -> String {
+    match &self.classifier {
+      Some(classifier) => format!("{}:{}:{}", self.group, self.artifact, classifier),
+      None => format!("{}:{}", self.group, self.artifact)
+    }
+  }
+
+  /// Name of the artifact file (e.g. ``lwjgl-3.3.1-natives-linux.jar``).
+  pub fn file_name(&self) -> String {
+    match &self.classifier {
+      Some(classifier) => format!("{}-{}-{}.{}", self.artifact, self.version, classifier, self.extension),
+      None => format!("{}-{}.{}", self.artifact, self.version, self.extension)
+    }
+  }
+
+  /// Path of the artifact relative to the repository root (e.g. ``org/lwjgl/lwjgl/3.3.1/lwjgl-3.3.1-natives-linux.jar``).
+  pub fn to_path(&self) -> PathBuf {
+    let mut path = self.group.split('.')
+      .fold(PathBuf::new(), |path, part| path.join(part));
+
+    path.push(&self.artifact);
+    path.push(&self.version);
+    path.push(self.file_name());
+
+    path
+  }
+
+  /// Same as [`MavenCoordinate::to_path`], but separated with ``/`` to be used in URLs.
+  pub fn to_url_path(&self) -> String {
+    format!("{}/{}/{}/{}", self.group.replace('.', "/"), self.artifact, self.version, self.file_name())
+  }
+}
+
+/// Converts relative path from ``downloads.artifact.path`` (always separated with ``/``) to the OS path.\
+/// Paths leaving the folder (``..``, absolute paths, Windows prefixes) are rejected, since they come from remote files.
+pub(crate) fn artifact_path(path: &str) -> io::Result<PathBuf> {
+  // every part is a single folder or file name, so "C:", "\\" or ".." can't change the base folder
+  let is_normal = |part: &str| {
+    let mut components = Path::new(part).components();
+    matches!((components.next(), components.next()), (Some(Component::Normal(_)), None))
+  };
+
+  if !path.split('/').all(is_normal) {
+    return Err(Error::new(ErrorKind::InvalidData, format!("Invalid relative path: {}", path)));
+  }
+
+  Ok(path.split('/')
+    .fold(PathBuf::new(), |result, part| result.join(Path::new(part))))
+}
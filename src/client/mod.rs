@@ -1,32 +1,68 @@
 mod argument;
-mod assetindex;
+pub mod assetindex;
 pub(crate) mod classpath;
-mod downloads;
-mod javaversion;
-mod library;
-mod rules;
+pub mod downloads;
+pub mod inheritance;
+pub mod javaversion;
+pub mod library;
+pub mod logging;
+pub mod maven;
+pub mod natives;
+pub mod releasetype;
+pub mod timestamp;
+pub mod rules;
 
 pub(crate) trait CollectArguments {
-  fn collect(&self) -> Vec<String>;
+  fn collect(&self, context: &rules::RuleContext) -> Vec<String>;
 }
 
 use std::fs::File;
 use std::io;
 use std::io::{Error, ErrorKind, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use crate::client::argument::Arguments;
 use crate::client::assetindex::AssetIndex;
 use crate::client::downloads::Downloads;
 use crate::client::javaversion::ClientJavaVersion;
 use crate::client::library::Library;
+use crate::client::logging::Logging;
+use crate::client::releasetype::ReleaseType;
+use crate::client::timestamp::Timestamp;
+use crate::minecraft::configuration::{LogFormat, MinecraftConfiguration as Configuration};
+
+/// Returns file of the version ``version`` with extension ``extension``.
+///
+/// Both ``versions/<version>/client.<extension>`` and vanilla ``versions/<version>/<version>.<extension>`` layouts are supported,
+/// first one is returned if none of them exist.
+pub(crate) fn find_version_file(versions_dir: &Path, version: &str, extension: &str) -> PathBuf {
+  let version_dir = versions_dir.join(version);
+  let client_file = version_dir.join(format!("client.{}", extension));
+
+  if client_file.is_file() {
+    return client_file;
+  }
+
+  let vanilla_file = version_dir.join(format!("{}.{}", version, extension));
+
+  if vanilla_file.is_file() {
+    return vanilla_file;
+  }
+
+  client_file
+}
 
 /// Implementation of ``client.json`` files structure
 ///
 /// Reference: https://minecraft.fandom.com/wiki/Client.json
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ClientFile {
-  pub arguments: Arguments,
+  /// Arguments of the game and JVM (1.13 and above).
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub arguments: Option<Arguments>,
+  /// Space-separated game arguments of legacy versions (1.12.2 and below).
+  #[serde(rename = "minecraftArguments", default, skip_serializing_if = "Option::is_none")]
+  pub minecraft_arguments: Option<String>,
   #[serde(rename = "assetIndex")]
   pub asset_index: AssetIndex,
   pub assets: String,
@@ -37,18 +73,31 @@ pub struct ClientFile {
   pub downloads: Downloads,
   /// The name of this version client (e.g. 1.14.4).
   pub id: String,
-  #[serde(rename = "javaVersion")]
-  pub java_version: ClientJavaVersion,
+  /// The name of the version this one inherits from (e.g. Forge or Fabric inherits from vanilla version).\
+  /// Is resolved by [`inheritance::ClientResolver`].
+  #[serde(rename = "inheritsFrom", default, skip_serializing_if = "Option::is_none")]
+  pub inherits_from: Option<String>,
+  /// The name of the version whose jar is used to run this one.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub jar: Option<String>,
+  /// Java required by the version, old client files don't have it (see [`ClientFile::get_java_version`]).
+  #[serde(rename = "javaVersion", default, skip_serializing_if = "Option::is_none")]
+  pub java_version: Option<ClientJavaVersion>,
   pub libraries: Vec<Library>,
-  // logging: ,
+  /// Information about log4j configuration of the client.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub logging: Option<Logging>,
   #[serde(rename = "mainClass")]
   pub main_class: String,
   #[serde(rename = "minimumLauncherVersion")]
   pub minimum_launcher_version: usize,
+  /// The release time of the version.
   #[serde(rename = "releaseTime")]
-  pub release_time: String,
-  pub time: String,
-  pub r#type: String
+  pub release_time: Timestamp,
+  /// The time when the client file was updated.
+  pub time: Timestamp,
+  /// The type of the version.
+  pub r#type: ReleaseType
 }
 
 impl ClientFile {
@@ -63,4 +112,33 @@ impl ClientFile {
 
     Ok(serde_json::from_str(&content)?)
   }
+
+  /// Returns Java required by the version, it's inferred from the release time if client file doesn't specify it.
+  pub fn get_java_version(&self) -> ClientJavaVersion {
+    self.java_version.clone()
+      .unwrap_or_else(|| ClientJavaVersion::infer(self.release_time))
+  }
+
+  /// Returns arguments of the client regardless of the version format.
+  ///
+  /// Legacy ``minecraftArguments`` string is converted via [`Arguments::from_legacy`].\
+  /// Log4j configuration argument is added if ``config`` asks for XML logs and config file is present.
+  pub fn get_arguments(&self, config: &Configuration) -> io::Result<Arguments> {
+    let mut arguments = match (&self.arguments, &self.minecraft_arguments) {
+      (Some(arguments), _) => arguments.clone(),
+      (None, Some(legacy)) => Arguments::from_legacy(legacy),
+      (None, None) => return Err(Error::new(ErrorKind::InvalidData, "Client file has neither arguments nor minecraftArguments"))
+    };
+
+    let logging = self.logging.as_ref()
+      .and_then(|logging| logging.client.as_ref());
+
+    if let (LogFormat::Xml, Some(logging)) = (&config.log_format, logging) {
+      if let Some(argument) = logging.get_argument(&config.client.get_assets_dir()) {
+        arguments.add_jvm(&argument);
+      }
+    }
+
+    Ok(arguments)
+  }
 }
\ No newline at end of file
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use zip::ZipArchive;
+use crate::minecraft::configuration::MinecraftConfiguration as Configuration;
+use crate::progress::{NoProgress, Phase, ProgressEvent, ProgressSink};
+use super::rules::RuleContext;
+use super::ClientFile;
+
+/// Name of the file in natives folder, that remembers which jars were extracted
+const EXTRACTED_FILE: &str = ".extracted.json";
+
+/// Unpacks native libraries (``.dll``, ``.so``, ``.dylib``) from classifier jars into natives folder.
+pub struct NativesExtractor;
+
+impl NativesExtractor {
+  /// Extracts natives of every library allowed on the current OS into ``versions/<version>/natives``.
+  ///
+  /// Jars that weren't changed since the previous extraction are skipped.
+  pub fn extract(config: &Configuration, client_file: &ClientFile) -> io::Result<()> {
+    Self::extract_with_progress(config, client_file, &NoProgress)
+  }
+
+  /// Same as [`NativesExtractor::extract`], every native jar is reported as a file.
+  pub fn extract_with_progress(config: &Configuration, client_file: &ClientFile, progress: &dyn ProgressSink) -> io::Result<()> {
+    let context = RuleContext::from(config);
+    let libraries_dir = config.client.get_libraries_dir();
+    let natives_dir = config.client.get_natives_dir();
+
+    let mut jars = Vec::new();
+
+    for lib in client_file.libraries.iter().filter(|lib| lib.is_allowed(&context)) {
+      if let Some(path) = lib.get_natives_path(&context)? {
+        let exclude = lib.extract.as_ref()
+          .and_then(|extract| extract.get("exclude"))
+          .cloned()
+          .unwrap_or_default();
+
+        jars.push((libraries_dir.join(path), exclude));
+      }
+    }
+
+    progress.event(ProgressEvent::PhaseStarted {
+      phase: Phase::Natives,
+      files: jars.len(),
+      bytes: jars.iter()
+        .filter_map(|(jar, _)| fs::metadata(jar).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+    });
+
+    let result = extract_jars(&jars, &natives_dir, progress);
+    progress.event(ProgressEvent::PhaseFinished { phase: Phase::Natives });
+
+    result
+  }
+}
+
+fn extract_jars(jars: &[(PathBuf, Vec<String>)], natives_dir: &Path, progress: &dyn ProgressSink) -> io::Result<()> {
+  fs::create_dir_all(natives_dir)?;
+
+  let extracted_file = natives_dir.join(EXTRACTED_FILE);
+  let mut extracted: HashMap<String, String> = fs::read_to_string(&extracted_file)
+    .ok()
+    .and_then(|content| serde_json::from_str(&content).ok())
+    .unwrap_or_default();
+
+  for (jar, exclude) in jars {
+    progress.event(ProgressEvent::FileStarted { path: jar.clone(), size: None });
+
+    let result = extract_jar(jar, natives_dir, exclude, &mut extracted);
+
+    progress.event(match &result {
+      Ok(()) => ProgressEvent::FileFinished { path: jar.clone() },
+      Err(e) => ProgressEvent::FileFailed { path: jar.clone(), error: e.to_string() }
+    });
+
+    result?;
+  }
+
+  fs::write(extracted_file, serde_json::to_string(&extracted)?)
+}
+
+/// Unpacks the jar, if it was changed since the previous extraction
+fn extract_jar(jar: &Path, natives_dir: &Path, exclude: &[String], extracted: &mut HashMap<String, String>) -> io::Result<()> {
+  let key = jar.to_string_lossy().to_string();
+  let fingerprint = fingerprint(jar)?;
+
+  if extracted.get(&key) == Some(&fingerprint) {
+    return Ok(());
+  }
+
+  unzip(jar, natives_dir, exclude)?;
+  extracted.insert(key, fingerprint);
+
+  Ok(())
+}
+
+/// Size and modification time of the file, changes whenever the jar is replaced
+fn fingerprint(path: &Path) -> io::Result<String> {
+  let metadata = fs::metadata(path)
+    .map_err(|e| Error::new(e.kind(), format!("Unable to read native library {}: {}", path.display(), e)))?;
+
+  let modified = metadata.modified()?
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_nanos())
+    .unwrap_or_default();
+
+  Ok(format!("{}-{}", metadata.len(), modified))
+}
+
+fn unzip(jar: &Path, destination: &Path, exclude: &[String]) -> io::Result<()> {
+  let mut archive = ZipArchive::new(File::open(jar)?)
+    .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+
+  for index in 0..archive.len() {
+    let mut entry = archive.by_index(index)
+      .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+
+    if entry.is_dir() || exclude.iter().any(|prefix| entry.name().starts_with(prefix.as_str())) {
+      continue;
+    }
+
+    // entries with absolute paths or ``..`` are skipped
+    let Some(name) = entry.enclosed_name() else {
+      continue;
+    };
+
+    let path = destination.join(name);
+
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    io::copy(&mut entry, &mut File::create(path)?)?;
+  }
+
+  Ok(())
+}
@@ -0,0 +1,56 @@
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+/// Type of the version (``type`` field of client files and version manifest)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum ReleaseType {
+  Release,
+  Snapshot,
+  OldBeta,
+  OldAlpha,
+  /// Types that are not used by Mojang (e.g. ``modified``)
+  Other(String)
+}
+
+impl FromStr for ReleaseType {
+  type Err = Infallible;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Ok(match s {
+      "release" => Self::Release,
+      "snapshot" => Self::Snapshot,
+      "old_beta" => Self::OldBeta,
+      "old_alpha" => Self::OldAlpha,
+      other => Self::Other(other.to_string())
+    })
+  }
+}
+
+impl From<String> for ReleaseType {
+  fn from(value: String) -> Self {
+    let Ok(kind) = value.parse();
+    kind
+  }
+}
+
+impl Display for ReleaseType {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let str = match self {
+      Self::Release => "release",
+      Self::Snapshot => "snapshot",
+      Self::OldBeta => "old_beta",
+      Self::OldAlpha => "old_alpha",
+      Self::Other(other) => other
+    };
+    write!(f, "{}", str)
+  }
+}
+
+impl From<ReleaseType> for String {
+  fn from(value: ReleaseType) -> Self {
+    value.to_string()
+  }
+}
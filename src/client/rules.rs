@@ -1,5 +1,9 @@
 use std::env;
+use std::sync::LazyLock;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use crate::minecraft::configuration::{MinecraftConfiguration as Configuration, QuickPlay};
+use crate::os::{version::OsVersionReader, OsAbstraction};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum OsKind {
@@ -25,16 +29,68 @@ impl Default for OsKind {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct OsSpecifier {
-  pub name: Option<OsKind>
+  pub name: Option<OsKind>,
+  /// Regex, that should match version of the OS (e.g. ``^10\\.`` for Windows 10).
+  pub version: Option<String>,
+  /// Architecture of the OS (e.g. ``x86``).
+  pub arch: Option<String>
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl OsSpecifier {
+  fn matches(&self, context: &RuleContext) -> bool {
+    if let Some(name) = &self.name {
+      if name != &context.os {
+        return false;
+      }
+    }
+
+    if let Some(version) = &self.version {
+      // invalid regex can't match anything
+      let matched = Regex::new(version)
+        .map(|regex| regex.is_match(&context.os_version))
+        .unwrap_or_default();
+
+      if !matched {
+        return false;
+      }
+    }
+
+    match &self.arch {
+      Some(arch) => arch == &context.arch,
+      None => true
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct RuleFeatures {
-  is_demo_user: Option<bool>,
-  has_custom_resolution: Option<bool>
+  pub is_demo_user: Option<bool>,
+  pub has_custom_resolution: Option<bool>,
+  pub has_quick_plays_support: Option<bool>,
+  pub is_quick_play_singleplayer: Option<bool>,
+  pub is_quick_play_multiplayer: Option<bool>,
+  pub is_quick_play_realms: Option<bool>
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+impl RuleFeatures {
+  /// Every feature specified in ``self`` should have the same value in ``enabled``.\
+  /// Features that are not specified in ``enabled`` are disabled.
+  fn matches(&self, enabled: &RuleFeatures) -> bool {
+    let pairs = [
+      (self.is_demo_user, enabled.is_demo_user),
+      (self.has_custom_resolution, enabled.has_custom_resolution),
+      (self.has_quick_plays_support, enabled.has_quick_plays_support),
+      (self.is_quick_play_singleplayer, enabled.is_quick_play_singleplayer),
+      (self.is_quick_play_multiplayer, enabled.is_quick_play_multiplayer),
+      (self.is_quick_play_realms, enabled.is_quick_play_realms)
+    ];
+
+    pairs.into_iter()
+      .all(|(required, enabled)| required.is_none_or(|required| required == enabled.unwrap_or_default()))
+  }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum RuleAction {
   #[serde(rename = "allow")]
   Allow,
@@ -50,19 +106,87 @@ pub struct Rule {
 }
 
 impl Rule {
-  /// Is the rule being followed?
-  /// TODO @ make features support
+  /// Does the rule apply in ``context``?\
+  /// Action of the rule is not taken into account.
+  pub fn matches(&self, context: &RuleContext) -> bool {
+    let os = self.os.as_ref()
+      .is_none_or(|os| os.matches(context));
+
+    let features = self.features.as_ref()
+      .is_none_or(|features| features.matches(&context.features));
+
+    os && features
+  }
+
+  /// Is the rule being followed on the current OS without any features?
   pub fn is_followed(&self) -> bool {
-    match &self.os {
-      // lol
-      Some(OsSpecifier{ name: Some(os)  }) => {
-        let current_os = OsKind::default();
-        match self.action {
-          RuleAction::Allow => os == &current_os,
-          RuleAction::Disallow => os != &current_os,
-        }
-      },
-      _ => true
+    RuleContext::default().allows(std::slice::from_ref(self))
+  }
+}
+
+static OS_VERSION: LazyLock<String> = LazyLock::new(OsAbstraction::os_version);
+
+/// Environment against which rules are evaluated.
+#[derive(Debug, Clone)]
+pub struct RuleContext {
+  /// Current OS
+  pub os: OsKind,
+  /// Version of the OS (e.g. ``10.0.19045`` on Windows or kernel release on Linux)
+  pub os_version: String,
+  /// Architecture in terms of client files (``x86``, ``x86_64``, ``arm64``)
+  pub arch: String,
+  /// Enabled features
+  pub features: RuleFeatures
+}
+
+impl Default for RuleContext {
+  fn default() -> Self {
+    let arch = match env::consts::ARCH {
+      "aarch64" => "arm64",
+      arch => arch
+    };
+
+    Self {
+      os: OsKind::default(),
+      os_version: OS_VERSION.clone(),
+      arch: arch.to_string(),
+      features: RuleFeatures::default()
+    }
+  }
+}
+
+impl From<&Configuration> for RuleContext {
+  fn from(config: &Configuration) -> Self {
+    let quick_play = config.client.quick_play.as_ref();
+
+    let features = RuleFeatures {
+      is_demo_user: Some(config.session.demo),
+      has_custom_resolution: Some(config.window.width.is_some() && config.window.height.is_some()),
+      has_quick_plays_support: Some(config.client.quick_play_path.is_some()),
+      is_quick_play_singleplayer: Some(matches!(quick_play, Some(QuickPlay::Singleplayer(_)))),
+      is_quick_play_multiplayer: Some(matches!(quick_play, Some(QuickPlay::Multiplayer(_)))),
+      is_quick_play_realms: Some(matches!(quick_play, Some(QuickPlay::Realms(_))))
+    };
+
+    Self {
+      features,
+      ..Default::default()
+    }
+  }
+}
+
+impl RuleContext {
+  /// Evaluates rules the way the official launcher does:\
+  /// the last rule that matches the context decides, nothing is allowed if none of them match.\
+  /// Empty list of rules allows everything.
+  pub fn allows(&self, rules: &[Rule]) -> bool {
+    if rules.is_empty() {
+      return true;
     }
+
+    rules.iter()
+      .rev()
+      .find(|rule| rule.matches(self))
+      .is_some_and(|rule| rule.action == RuleAction::Allow)
   }
-}
\ No newline at end of file
+}
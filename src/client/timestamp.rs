@@ -0,0 +1,127 @@
+use std::fmt::Display;
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+/// Point in time from client files and version manifest (e.g. ``2017-09-18T08:39:46+00:00``).
+///
+/// Stored as seconds since Unix epoch in UTC, so timestamps with different offsets are comparable.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+  /// Creates timestamp from seconds since Unix epoch.
+  pub fn from_unix(seconds: i64) -> Self {
+    Self(seconds)
+  }
+
+  /// Seconds since Unix epoch.
+  pub fn unix(&self) -> i64 {
+    self.0
+  }
+}
+
+/// Days since 1970-01-01 of the given date (proleptic Gregorian calendar)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+  let year = if month <= 2 { year - 1 } else { year };
+  let era = year.div_euclid(400);
+  let year_of_era = year - era * 400;
+  let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+  let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+  era * 146097 + day_of_era - 719468
+}
+
+/// (year, month, day) of the given days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+  let days = days + 719468;
+  let era = days.div_euclid(146097);
+  let day_of_era = days - era * 146097;
+  let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let month_part = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * month_part + 2) / 5 + 1;
+  let month = if month_part < 10 { month_part + 3 } else { month_part - 9 };
+  let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+  (year, month, day)
+}
+
+impl FromStr for Timestamp {
+  type Err = Error;
+
+  /// Parses ``YYYY-MM-DDTHH:MM:SS[.fraction](Z|±HH:MM|±HHMM)``
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let invalid = || Error::new(ErrorKind::InvalidData, format!("Invalid timestamp: {}", s));
+    let number = |part: &str| part.parse::<i64>().map_err(|_| invalid());
+
+    let (date, time) = s.split_once(['T', ' ']).ok_or_else(invalid)?;
+
+    let date = date.split('-').collect::<Vec<&str>>();
+    let [year, month, day] = date[..] else {
+      return Err(invalid());
+    };
+
+    // offset starts with the first sign or Z after the seconds
+    let offset_start = time.find(['+', '-', 'Z']).unwrap_or(time.len());
+    let (time, offset) = time.split_at(offset_start);
+
+    let time = time.split('.').next().unwrap_or_default()
+      .split(':')
+      .collect::<Vec<&str>>();
+
+    let [hours, minutes, seconds] = time[..] else {
+      return Err(invalid());
+    };
+
+    let offset = match offset {
+      "" | "Z" => 0,
+      offset => {
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let digits = offset[1..].replace(':', "");
+
+        // checked before slicing, non-ASCII characters would panic
+        if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+          return Err(invalid());
+        }
+
+        sign * (number(&digits[..2])? * 3600 + number(&digits[2..])? * 60)
+      }
+    };
+
+    let (month, day) = (number(month)?, number(day)?);
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+      return Err(invalid());
+    }
+
+    let days = days_from_civil(number(year)?, month, day);
+    let seconds = days * 86400 + number(hours)? * 3600 + number(minutes)? * 60 + number(seconds)?;
+
+    Ok(Self(seconds - offset))
+  }
+}
+
+impl TryFrom<String> for Timestamp {
+  type Error = Error;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    value.parse()
+  }
+}
+
+impl Display for Timestamp {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let (year, month, day) = civil_from_days(self.0.div_euclid(86400));
+    let seconds = self.0.rem_euclid(86400);
+
+    write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}+00:00", year, month, day, seconds / 3600, seconds % 3600 / 60, seconds % 60)
+  }
+}
+
+impl From<Timestamp> for String {
+  fn from(value: Timestamp) -> Self {
+    value.to_string()
+  }
+}
@@ -0,0 +1,83 @@
+use std::fmt::Debug;
+
+/// Turns URL of a file into the list of URLs it should be downloaded from, in order of preference.
+pub trait UrlRewriter: Debug + Send + Sync {
+  fn rewrite(&self, url: &str) -> Vec<String>;
+}
+
+/// Replaces ``from`` prefix of URLs with each of ``to`` prefixes.
+#[derive(Debug, Clone)]
+pub struct MirrorRule {
+  /// Prefix of the original URL (e.g. ``https://libraries.minecraft.net/``).
+  pub from: String,
+  /// Prefixes of mirrors, that are tried in the given order.
+  pub to: Vec<String>,
+  /// Try the original URL after all mirrors fail.
+  pub keep_original: bool
+}
+
+impl MirrorRule {
+  pub fn new(from: &str, to: &[&str]) -> Self {
+    Self {
+      from: from.to_string(),
+      to: to.iter().map(|mirror| mirror.to_string()).collect(),
+      keep_original: true
+    }
+  }
+}
+
+/// Rewrites URLs by the first matching rule, URLs without matching rules are left as is.
+///
+/// Custom maven repositories are rules too:
+/// ```rs
+/// MirrorConfiguration {
+///   rules: vec![MirrorRule::new("https://libraries.minecraft.net/", &["https://maven.example.com/"])]
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MirrorConfiguration {
+  pub rules: Vec<MirrorRule>
+}
+
+const BMCLAPI: &str = "https://bmclapi2.bangbang93.com/";
+const BMCLAPI_MAVEN: &str = "https://bmclapi2.bangbang93.com/maven/";
+
+impl MirrorConfiguration {
+  /// Mirrors of [BMCLAPI](https://bmclapidoc.bangbang93.com/), the original hosts are used as fallback.
+  pub fn bmclapi() -> Self {
+    Self {
+      rules: vec![
+        MirrorRule::new("https://piston-meta.mojang.com/", &[BMCLAPI]),
+        MirrorRule::new("https://piston-data.mojang.com/", &[BMCLAPI]),
+        MirrorRule::new("https://launchermeta.mojang.com/", &[BMCLAPI]),
+        MirrorRule::new("https://launcher.mojang.com/", &[BMCLAPI]),
+        MirrorRule::new("https://resources.download.minecraft.net/", &["https://bmclapi2.bangbang93.com/assets/"]),
+        MirrorRule::new("https://libraries.minecraft.net/", &[BMCLAPI_MAVEN]),
+        MirrorRule::new("https://maven.minecraftforge.net/", &[BMCLAPI_MAVEN]),
+        MirrorRule::new("https://files.minecraftforge.net/maven/", &[BMCLAPI_MAVEN]),
+        MirrorRule::new("https://maven.fabricmc.net/", &[BMCLAPI_MAVEN])
+      ]
+    }
+  }
+}
+
+impl UrlRewriter for MirrorConfiguration {
+  fn rewrite(&self, url: &str) -> Vec<String> {
+    let rule = self.rules.iter()
+      .find_map(|rule| url.strip_prefix(rule.from.as_str()).map(|rest| (rule, rest)));
+
+    let Some((rule, rest)) = rule else {
+      return vec![url.to_string()];
+    };
+
+    let mut result = rule.to.iter()
+      .map(|mirror| format!("{}{}", mirror, rest))
+      .collect::<Vec<String>>();
+
+    if rule.keep_original {
+      result.push(url.to_string());
+    }
+
+    result
+  }
+}
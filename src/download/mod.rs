@@ -0,0 +1,344 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Error, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use ureq::Agent;
+use crate::client::downloads::DownloadableObject;
+use crate::client::library::LibraryArtifact;
+use crate::minecraft::plan::{DownloadEntry, DownloadPlan};
+use crate::progress::{NoProgress, Phase, ProgressEvent, ProgressSink};
+use crate::utils::file_sha1;
+
+pub mod mirror;
+
+use mirror::{MirrorConfiguration, UrlRewriter};
+
+/// File that should be downloaded
+#[derive(Debug, Clone)]
+pub struct DownloadTask {
+  pub url: String,
+  /// URLs that are tried after ``url`` fails, in the given order.
+  pub mirrors: Vec<String>,
+  /// Where the file should be saved.
+  pub path: PathBuf,
+  /// Expected SHA1 of the file, it's not checked if ``None``.
+  pub sha1: Option<String>,
+  /// Expected size of the file, it's not checked if ``None``.
+  pub size: Option<u64>
+}
+
+impl DownloadTask {
+  pub fn from_object(object: &DownloadableObject, path: PathBuf) -> Self {
+    Self {
+      url: object.url.clone(),
+      mirrors: Vec::new(),
+      path,
+      sha1: Some(object.sha1.clone()),
+      size: Some(object.size as u64)
+    }
+  }
+
+  pub fn from_artifact(artifact: &LibraryArtifact, path: PathBuf) -> Self {
+    Self {
+      url: artifact.url.clone(),
+      mirrors: Vec::new(),
+      path,
+      sha1: Some(artifact.sha1.clone()),
+      size: Some(artifact.size as u64)
+    }
+  }
+
+  /// Is the file already downloaded and valid?
+  fn is_valid(&self, path: &Path) -> io::Result<bool> {
+    let Ok(metadata) = fs::metadata(path) else {
+      return Ok(false);
+    };
+
+    if self.size.is_some_and(|size| size != metadata.len()) {
+      return Ok(false);
+    }
+
+    match &self.sha1 {
+      Some(sha1) => Ok(file_sha1(path)?.eq_ignore_ascii_case(sha1)),
+      None => Ok(true)
+    }
+  }
+
+  /// ``<file>.part``, where the file is downloaded before it's verified
+  fn part_path(&self) -> PathBuf {
+    let mut name = self.path.file_name()
+      .unwrap_or_default()
+      .to_os_string();
+    name.push(".part");
+
+    self.path.with_file_name(name)
+  }
+}
+
+impl TryFrom<&DownloadEntry> for DownloadTask {
+  type Error = Error;
+
+  fn try_from(entry: &DownloadEntry) -> Result<Self, Self::Error> {
+    let url = entry.url.clone()
+      .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("URL of {} is unknown", entry.path.display())))?;
+
+    Ok(Self {
+      url,
+      mirrors: entry.mirrors.clone(),
+      path: entry.path.clone(),
+      sha1: entry.sha1.clone(),
+      size: entry.size
+    })
+  }
+}
+
+/// Downloads files over HTTP in parallel.
+///
+/// Files are written to ``<file>.part``, verified by size and SHA1 and then renamed,
+/// so a file is never present half-downloaded. Partial files are resumed with ``Range`` requests.
+///
+/// Every URL goes through ``rewriter``, so mirrors can be used instead of Mojang's hosts:
+/// ```rs
+/// let downloader = Downloader {
+///   rewriter: Arc::new(MirrorConfiguration::bmclapi()),
+///   ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct Downloader {
+  /// How many files are downloaded at the same time
+  pub concurrency: usize,
+  /// How many times a file is retried after a failure
+  pub retries: u32,
+  /// Delay before the first retry, doubles with every next retry
+  pub backoff: Duration,
+  /// Timeout of connecting to the server
+  pub connect_timeout: Duration,
+  /// Longest wait for the next bytes of a response, the whole download isn't limited
+  pub read_timeout: Duration,
+  /// Turns URL of every file into URLs of its mirrors, doesn't change anything by default
+  pub rewriter: Arc<dyn UrlRewriter>,
+  /// Receives events of every download
+  pub progress: Arc<dyn ProgressSink>
+}
+
+impl Default for Downloader {
+  fn default() -> Self {
+    Self {
+      concurrency: 8,
+      retries: 3,
+      backoff: Duration::from_millis(500),
+      connect_timeout: Duration::from_secs(10),
+      read_timeout: Duration::from_secs(30),
+      rewriter: Arc::new(MirrorConfiguration::default()),
+      progress: Arc::new(NoProgress)
+    }
+  }
+}
+
+impl Downloader {
+  fn agent(&self) -> Agent {
+    ureq::AgentBuilder::new()
+      .timeout_connect(self.connect_timeout)
+      .timeout_read(self.read_timeout)
+      .build()
+  }
+
+  /// Downloads a single file.
+  pub fn download(&self, task: &DownloadTask) -> io::Result<()> {
+    self.download_with(&self.agent(), task)
+  }
+
+  /// Downloads every file, ``concurrency`` files at once.
+  ///
+  /// All files are tried even if some of them fail, the error describes every failed file.
+  pub fn download_all(&self, tasks: &[DownloadTask]) -> io::Result<()> {
+    let agent = self.agent();
+    let next = AtomicUsize::new(0);
+    let errors = Mutex::new(Vec::new());
+
+    self.progress.event(ProgressEvent::PhaseStarted {
+      phase: Phase::Download,
+      files: tasks.len(),
+      bytes: tasks.iter().filter_map(|task| task.size).sum()
+    });
+
+    thread::scope(|scope| {
+      for _ in 0..self.concurrency.clamp(1, tasks.len().max(1)) {
+        scope.spawn(|| {
+          while let Some(task) = tasks.get(next.fetch_add(1, Ordering::Relaxed)) {
+            if let Err(e) = self.download_with(&agent, task) {
+              errors.lock().unwrap().push(format!("{}: {}", task.url, e));
+            }
+          }
+        });
+      }
+    });
+
+    self.progress.event(ProgressEvent::PhaseFinished { phase: Phase::Download });
+
+    let errors = errors.into_inner().unwrap();
+
+    if errors.is_empty() {
+      return Ok(());
+    }
+
+    Err(Error::other(format!("Failed to download {} files: {}", errors.len(), errors.join("; "))))
+  }
+
+  /// Downloads every missing file of the plan.
+  ///
+  /// Files with unknown URL can't be downloaded, they are listed in the error after other files are downloaded.\
+  /// Asset objects aren't in the plan until the asset index is present (see [`DownloadPlan::new`]),
+  /// so the plan is computed and downloaded again after the first download of a version.
+  pub fn download_plan(&self, plan: &DownloadPlan) -> io::Result<()> {
+    let mut tasks = Vec::new();
+    let mut unknown = Vec::new();
+
+    for entry in plan.missing() {
+      match DownloadTask::try_from(entry) {
+        Ok(task) => tasks.push(task),
+        Err(_) => unknown.push(entry.path.display().to_string())
+      }
+    }
+
+    let result = self.download_all(&tasks);
+
+    if unknown.is_empty() {
+      return result;
+    }
+
+    let message = format!("URLs of {} files are unknown: {}", unknown.len(), unknown.join("; "));
+
+    match result {
+      Ok(()) => Err(Error::new(ErrorKind::NotFound, message)),
+      Err(e) => Err(Error::new(e.kind(), format!("{}; {}", e, message)))
+    }
+  }
+
+  fn download_with(&self, agent: &Agent, task: &DownloadTask) -> io::Result<()> {
+    self.progress.event(ProgressEvent::FileStarted { path: task.path.clone(), size: task.size });
+
+    let result = self.download_file(agent, task);
+
+    self.progress.event(match &result {
+      Ok(()) => ProgressEvent::FileFinished { path: task.path.clone() },
+      Err(e) => ProgressEvent::FileFailed { path: task.path.clone(), error: e.to_string() }
+    });
+
+    result
+  }
+
+  fn download_file(&self, agent: &Agent, task: &DownloadTask) -> io::Result<()> {
+    if task.is_valid(&task.path)? {
+      return Ok(());
+    }
+
+    let urls = self.urls(task);
+    let mut attempt = 0;
+
+    loop {
+      let mut result = Err(Error::new(ErrorKind::NotFound, "No URLs to download from"));
+
+      // every mirror is tried before waiting for the next attempt
+      for url in &urls {
+        result = self.try_download(agent, task, url);
+
+        if result.is_ok() {
+          return Ok(());
+        }
+      }
+
+      if attempt >= self.retries {
+        return result;
+      }
+
+      thread::sleep(self.backoff * 2u32.pow(attempt));
+      attempt += 1;
+    }
+  }
+
+  /// Rewritten ``url`` and ``mirrors`` of the task, without duplicates
+  fn urls(&self, task: &DownloadTask) -> Vec<String> {
+    let mut urls = Vec::new();
+
+    for url in std::iter::once(&task.url).chain(&task.mirrors) {
+      for url in self.rewriter.rewrite(url) {
+        if !urls.contains(&url) {
+          urls.push(url);
+        }
+      }
+    }
+
+    urls
+  }
+
+  fn try_download(&self, agent: &Agent, task: &DownloadTask, url: &str) -> io::Result<()> {
+    if let Some(parent) = task.path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    let part = task.part_path();
+    let downloaded = fs::metadata(&part)
+      .map(|metadata| metadata.len())
+      .unwrap_or_default();
+
+    // part is already complete, but wasn't renamed
+    let complete = task.size.is_some_and(|size| downloaded >= size);
+
+    let mut request = agent.get(url);
+
+    if downloaded > 0 && !complete {
+      request = request.set("Range", &format!("bytes={}-", downloaded));
+    }
+
+    if !complete {
+      let response = match request.call() {
+        // server can't give the rest of the file, so it's downloaded again
+        Err(ureq::Error::Status(416, _)) => {
+          fs::remove_file(&part)?;
+          return Err(Error::new(ErrorKind::InvalidData, "Partial file is bigger than remote one"));
+        },
+        Err(e) => return Err(Error::other(e)),
+        Ok(response) => response
+      };
+
+      let (mut file, mut written) = match response.status() {
+        206 => (OpenOptions::new().append(true).open(&part)?, downloaded),
+        _ => (File::create(&part)?, 0)
+      };
+
+      let mut reader = response.into_reader();
+      let mut buffer = vec![0; 64 * 1024];
+
+      loop {
+        let read = reader.read(&mut buffer)?;
+
+        if read == 0 {
+          break;
+        }
+
+        file.write_all(&buffer[..read])?;
+        written += read as u64;
+
+        self.progress.event(ProgressEvent::BytesProgressed {
+          path: task.path.clone(),
+          downloaded: written,
+          total: task.size
+        });
+      }
+
+      file.sync_all()?;
+    }
+
+    if !task.is_valid(&part)? {
+      fs::remove_file(&part)?;
+      return Err(Error::new(ErrorKind::InvalidData, format!("Checksum mismatch of {}", task.path.display())));
+    }
+
+    fs::rename(part, &task.path)
+  }
+}
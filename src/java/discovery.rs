@@ -0,0 +1,121 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::os::{process::ProgramPathFinder, OsAbstraction};
+use crate::utils::choice_by_os;
+
+/// Folder of the user (``~``)
+fn home_dir() -> Option<PathBuf> {
+  env::var_os(choice_by_os("HOME", "USERPROFILE"))
+    .map(PathBuf::from)
+}
+
+/// Folder from the environment variable, or ``~/<default>``
+fn env_or_home(variable: &str, default: &str) -> Option<PathBuf> {
+  env::var_os(variable)
+    .map(PathBuf::from)
+    .or_else(|| home_dir().map(|home| home.join(default)))
+}
+
+/// Folders, whose subfolders are Java installations
+fn roots() -> Vec<PathBuf> {
+  let mut roots = vec![
+    env_or_home("SDKMAN_DIR", ".sdkman").map(|sdkman| sdkman.join("candidates").join("java")),
+    env_or_home("ASDF_DATA_DIR", ".asdf").map(|asdf| asdf.join("installs").join("java")),
+    env_or_home("GRADLE_USER_HOME", ".gradle").map(|gradle| gradle.join("jdks")),
+    home_dir().map(|home| home.join(".jdks"))
+  ];
+
+  if cfg!(target_os = "windows") {
+    for variable in ["ProgramFiles", "ProgramFiles(x86)"] {
+      if let Some(program_files) = env::var_os(variable).map(PathBuf::from) {
+        for vendor in ["Java", "Eclipse Adoptium", "Zulu", "Microsoft", "Amazon Corretto", "BellSoft"] {
+          roots.push(Some(program_files.join(vendor)));
+        }
+      }
+    }
+  } else {
+    roots.push(Some(PathBuf::from("/usr/lib/jvm")));
+    roots.push(Some(PathBuf::from("/usr/java")));
+    roots.push(Some(PathBuf::from("/opt")));
+    roots.push(Some(PathBuf::from("/Library/Java/JavaVirtualMachines")));
+    roots.push(home_dir().map(|home| home.join("Library").join("Java").join("JavaVirtualMachines")));
+  }
+
+  roots.into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Is ``bin/java`` present in the folder?
+fn is_java_home(path: &Path) -> bool {
+  path.join("bin")
+    .join(choice_by_os("java", "java.exe"))
+    .is_file()
+}
+
+/// Java home of the executable (``<home>/bin/java``), symlinks are resolved
+pub(crate) fn home_of_executable(executable: &Path) -> Option<PathBuf> {
+  let executable = fs::canonicalize(executable).ok()?;
+  let bin = executable.parent()?;
+
+  if bin.file_name()? != "bin" {
+    return None;
+  }
+
+  bin.parent()
+    .map(Path::to_path_buf)
+}
+
+/// Java homes in the subfolders of ``root``, including macOS bundles (``Contents/Home``)
+fn scan_root(root: &Path, homes: &mut Vec<PathBuf>) {
+  let Ok(entries) = fs::read_dir(root) else {
+    return;
+  };
+
+  for entry in entries.flatten() {
+    let path = entry.path();
+
+    for home in [path.join("Contents").join("Home"), path.join("jre.bundle").join("Contents").join("Home"), path] {
+      if is_java_home(&home) {
+        homes.push(home);
+        break;
+      }
+    }
+  }
+}
+
+/// Every folder, that looks like Java home, without duplicates.
+///
+/// ``JAVA_HOME`` goes first, then ``PATH``, then well-known folders and ``managed_dirs``.
+pub(crate) fn find_homes(managed_dirs: &[PathBuf]) -> Vec<PathBuf> {
+  let mut homes = Vec::new();
+
+  if let Some(java_home) = env::var_os("JAVA_HOME") {
+    homes.push(PathBuf::from(java_home));
+  }
+
+  for path in OsAbstraction::find_all_paths("java") {
+    if let Some(home) = home_of_executable(&path) {
+      homes.push(home);
+    }
+  }
+
+  for root in roots().iter().chain(managed_dirs) {
+    scan_root(root, &mut homes);
+  }
+
+  let mut result: Vec<PathBuf> = Vec::new();
+
+  for home in homes.into_iter().filter(|home| is_java_home(home)) {
+    let Ok(home) = fs::canonicalize(&home) else {
+      continue;
+    };
+
+    if !result.contains(&home) {
+      result.push(home);
+    }
+  }
+
+  result
+}
@@ -7,6 +7,8 @@ pub enum JavaErrorKind {
   OutputReadError,
   InvalidJavaFolderStructure,
   DifferentVersion,
+  /// None of Java installations fits the version, contains required major version
+  NoSuitableVersion(u8),
 }
 
 #[derive(Debug, Clone)]
@@ -48,6 +50,7 @@ impl From<JavaError> for std::io::Error {
       JavaErrorKind::OutputReadError => std::io::ErrorKind::InvalidInput,
       JavaErrorKind::InvalidJavaFolderStructure => std::io::ErrorKind::InvalidData,
       JavaErrorKind::DifferentVersion => std::io::ErrorKind::Other,
+      JavaErrorKind::NoSuitableVersion(_) => std::io::ErrorKind::NotFound,
     };
 
     Self::new(kind, v)
@@ -0,0 +1,254 @@
+use std::fs::{self, File};
+use std::io::{self, Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+use crate::utils::{file_sha1, file_sha256};
+use super::version::JavaVersion;
+use super::Java;
+
+/// Name of the file in runtimes folder, that lists installed archives
+const INDEX_FILE: &str = "installed.json";
+
+/// Expected checksum of an archive in hex
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum Checksum {
+  Sha1(String),
+  Sha256(String)
+}
+
+impl Checksum {
+  pub fn matches(&self, path: &Path) -> io::Result<bool> {
+    Ok(match self {
+      Self::Sha1(sha1) => file_sha1(path)?.eq_ignore_ascii_case(sha1),
+      Self::Sha256(sha256) => file_sha256(path)?.eq_ignore_ascii_case(sha256)
+    })
+  }
+}
+
+/// Archive with Java (``.tar.gz``, ``.tgz`` or ``.zip``)
+#[derive(Debug, Clone)]
+pub struct JavaArchive {
+  pub path: PathBuf,
+  /// Checksum from the publisher, the archive isn't checked if it's ``None``.
+  pub checksum: Option<Checksum>
+}
+
+/// Source of Java archives, e.g. Adoptium API or CDN of your launcher.
+pub trait JavaProvider {
+  /// Saves an archive of Java ``major`` version into ``dir``.
+  fn fetch(&self, major: u8, dir: &Path) -> io::Result<JavaArchive>;
+}
+
+/// Java installed by [`JavaInstaller`]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstalledJava {
+  /// Name of the folder in runtimes folder (e.g. ``jdk-21.0.2+13-jre``).
+  pub id: String,
+  /// Java home, differs from the folder for macOS layout (``Contents/Home``).
+  pub home: PathBuf,
+  pub version: JavaVersion,
+  pub vendor: Option<String>,
+  pub arch: Option<String>,
+  /// File name of the installed archive.
+  pub archive: String
+}
+
+/// Installs Java from archives into runtimes folder (see ``MinecraftClient::get_runtimes_dir``)
+/// and remembers installed ones in ``installed.json``.
+///
+/// Example
+/// ```rs
+/// let installer = JavaInstaller::new(config.client.get_runtimes_dir());
+/// let java = installer.install(&JavaArchive { path: "OpenJDK21U-jre_x64_linux_hotspot_21.0.2_13.tar.gz".into(), checksum: None })?;
+///
+/// for installed in installer.list()? {
+///   println!("{}: {}", installed.id, installed.version);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JavaInstaller {
+  pub runtimes_dir: PathBuf
+}
+
+impl JavaInstaller {
+  pub fn new(runtimes_dir: PathBuf) -> Self {
+    Self { runtimes_dir }
+  }
+
+  /// Fetches an archive from ``provider`` and installs it, the archive is removed after installation.
+  pub fn install_from(&self, provider: &dyn JavaProvider, major: u8) -> io::Result<Java> {
+    fs::create_dir_all(&self.runtimes_dir)?;
+
+    let archive = provider.fetch(major, &self.runtimes_dir)?;
+    let result = self.install(&archive);
+    let _ = fs::remove_file(&archive.path);
+
+    result
+  }
+
+  /// Verifies checksum of the archive and extracts it into ``<runtimes dir>/<top-level folder of the archive>``.\
+  /// Java installed from an archive with the same folder is replaced, it stays untouched if the archive is broken.
+  pub fn install(&self, archive: &JavaArchive) -> io::Result<Java> {
+    let file_name = archive.path.file_name()
+      .and_then(|name| name.to_str())
+      .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Archive has no file name"))?
+      .to_string();
+
+    if let Some(checksum) = &archive.checksum {
+      if !checksum.matches(&archive.path)? {
+        return Err(Error::new(ErrorKind::InvalidData, format!("Checksum mismatch of {}", file_name)));
+      }
+    }
+
+    let staging = self.runtimes_dir.join(format!(".{}.tmp", file_name));
+
+    if staging.exists() {
+      fs::remove_dir_all(&staging)?;
+    }
+
+    fs::create_dir_all(&staging)?;
+
+    let result = extract(&archive.path, &file_name, &staging)
+      .and_then(|()| self.replace_installation(&staging, &file_name));
+
+    let _ = fs::remove_dir_all(&staging);
+    let (id, java) = result?;
+
+    let mut installed = self.list()?;
+    installed.retain(|installed| installed.id != id);
+    installed.push(InstalledJava {
+      id,
+      home: java.get_path().to_path_buf(),
+      version: java.version().clone(),
+      vendor: java.release().vendor.clone(),
+      arch: java.release().arch.clone(),
+      archive: file_name
+    });
+
+    self.write_index(&installed)?;
+
+    Ok(java)
+  }
+
+  /// Java installed by this installer.
+  pub fn list(&self) -> io::Result<Vec<InstalledJava>> {
+    let index_file = self.runtimes_dir.join(INDEX_FILE);
+
+    if !index_file.is_file() {
+      return Ok(Vec::new());
+    }
+
+    Ok(serde_json::from_reader(File::open(index_file)?)?)
+  }
+
+  /// Removes Java by its ``InstalledJava::id``.
+  pub fn uninstall(&self, id: &str) -> io::Result<()> {
+    let mut installed = self.list()?;
+    let count = installed.len();
+    installed.retain(|installed| installed.id != id);
+
+    if installed.len() == count {
+      return Err(Error::new(ErrorKind::NotFound, format!("Java {} is not installed", id)));
+    }
+
+    let folder = self.runtimes_dir.join(id);
+
+    if folder.exists() {
+      fs::remove_dir_all(folder)?;
+    }
+
+    self.write_index(&installed)
+  }
+
+  fn write_index(&self, installed: &[InstalledJava]) -> io::Result<()> {
+    fs::write(self.runtimes_dir.join(INDEX_FILE), serde_json::to_string_pretty(installed)?)
+  }
+
+  /// Validates extracted files and moves them into their folder, archives usually contain a single top-level folder (``jdk-21.0.2+13-jre/``).\
+  /// Java with the same folder is kept if the new one can't be installed.
+  fn replace_installation(&self, staging: &Path, file_name: &str) -> io::Result<(String, Java)> {
+    let entries = fs::read_dir(staging)?
+      .collect::<io::Result<Vec<_>>>()?;
+
+    let (id, root) = match entries.as_slice() {
+      // macOS bundle without a top-level folder has only ``Contents/``
+      [entry] if entry.file_type()?.is_dir() && entry.file_name() != "Contents" => (entry.file_name().to_string_lossy().to_string(), entry.path()),
+      _ => (archive_stem(file_name).to_string(), staging.to_path_buf())
+    };
+
+    if id.is_empty() || id.starts_with('.') || id == INDEX_FILE {
+      return Err(Error::new(ErrorKind::InvalidData, format!("Invalid folder name {:?} in {}", id, file_name)));
+    }
+
+    // checked before the installed Java is touched
+    let home = find_home(&root)
+      .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("{} doesn't contain Java", file_name)))?;
+    Java::new(home.clone())?;
+
+    let home = home.strip_prefix(&root)
+      .unwrap_or(Path::new(""))
+      .to_path_buf();
+
+    let folder = self.runtimes_dir.join(&id);
+    let backup = self.runtimes_dir.join(format!(".{}.old", id));
+
+    if backup.exists() {
+      fs::remove_dir_all(&backup)?;
+    }
+
+    if folder.exists() {
+      fs::rename(&folder, &backup)?;
+    }
+
+    let result = fs::rename(&root, &folder)
+      .and_then(|()| Ok(Java::new(folder.join(&home))?));
+
+    match result {
+      Ok(java) => {
+        let _ = fs::remove_dir_all(&backup);
+        Ok((id, java))
+      },
+      Err(e) => {
+        let _ = fs::remove_dir_all(&folder);
+
+        if backup.exists() {
+          fs::rename(&backup, &folder)?;
+        }
+
+        Err(e)
+      }
+    }
+  }
+}
+
+/// ``OpenJDK21U-jre.tar.gz`` -> ``OpenJDK21U-jre``
+fn archive_stem(file_name: &str) -> &str {
+  [".tar.gz", ".tgz", ".zip"].iter()
+    .find_map(|extension| file_name.strip_suffix(extension))
+    .unwrap_or(file_name)
+}
+
+fn extract(archive: &Path, file_name: &str, destination: &Path) -> io::Result<()> {
+  let file = File::open(archive)?;
+
+  if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+    return tar::Archive::new(GzDecoder::new(file)).unpack(destination);
+  }
+
+  if file_name.ends_with(".zip") {
+    return ZipArchive::new(file)
+      .and_then(|mut zip| zip.extract(destination))
+      .map_err(|e| Error::new(ErrorKind::InvalidData, e));
+  }
+
+  Err(Error::new(ErrorKind::InvalidInput, format!("Unsupported archive {}, only .tar.gz and .zip are supported", file_name)))
+}
+
+/// Java home in the folder, macOS builds keep it in ``Contents/Home``
+fn find_home(folder: &Path) -> Option<PathBuf> {
+  [folder.to_path_buf(), folder.join("Contents").join("Home")]
+    .into_iter()
+    .find(|home| home.join("bin").is_dir())
+}
@@ -1,9 +1,15 @@
 use std::path::{Path, PathBuf};
 use error::{JavaError, JavaErrorKind, Result};
+use release::JavaRelease;
 use version::JavaVersion;
 use crate::{os::{process::ProgramPathFinder, OsAbstraction}, utils::choice_by_os};
 
+mod discovery;
 pub mod error;
+pub mod install;
+pub mod release;
+pub mod runtime;
+pub mod selector;
 pub mod version;
 
 pub const JAVA_SEPARATOR: &str = if cfg!(target_os = "windows") { ";" } else { ":" };
@@ -11,7 +17,7 @@ pub const JAVA_SEPARATOR: &str = if cfg!(target_os = "windows") { ";" } else { "
 #[derive(Debug, Clone)]
 pub struct Java {
   base_path: PathBuf,
-  version: JavaVersion
+  release: JavaRelease
 }
 
 impl TryFrom<String> for Java {
@@ -28,11 +34,11 @@ impl Java {
       return Err(JavaError::new(JavaErrorKind::NotFound));
     }
 
-    let version = JavaVersion::get_verison(&base_path)?;
+    let release = JavaRelease::detect(&base_path)?;
 
     Ok(Self {
       base_path,
-      version
+      release
     })
   }
 
@@ -45,21 +51,45 @@ impl Java {
 
     // if not - we tryna find java in system
 
-    // caching error instance
-    let java_err = JavaError::new(JavaErrorKind::InvalidJavaFolderStructure);
+    let paths = OsAbstraction::find_all_paths("java");
 
-    let path = OsAbstraction::find_path("java")?;
+    // java from PATH is usually a symlink (e.g. /usr/bin/java -> /usr/lib/jvm/<jdk>/bin/java), which is resolved already
+    let home = paths.iter()
+      .find_map(|path| discovery::home_of_executable(path))
+      .ok_or(match paths.first() {
+        Some(path) => JavaError::new_with_details(JavaErrorKind::InvalidJavaFolderStructure, format!("{} is not located in bin folder", path.display())),
+        None => JavaError::new_with_details(JavaErrorKind::NotFound, String::from("java is not found in PATH"))
+      })?;
 
-    let parent = path.ancestors()
-      .next().ok_or(java_err.clone())?
-      .file_name().ok_or(java_err.clone())?
-      .to_str().ok_or(java_err.clone())?;
+    Self::new(home)
+  }
 
-    if parent != "bin" {
-      return Err(java_err);
-    }
+  /// Finds every Java installation on the PC.
+  ///
+  /// ``JAVA_HOME``, ``PATH``, ``/usr/lib/jvm``, ``/opt``, SDKMAN, asdf, ``~/.jdks``, Gradle toolchains
+  /// and other well-known folders are scanned. Symlinks are resolved, so every installation is listed once.
+  ///
+  /// Example
+  /// ```rs
+  /// for java in Java::discover_all() {
+  ///   println!("{} - Java {}", java.get_path().display(), java.version().main_version());
+  /// }
+  /// ```
+  pub fn discover_all() -> Vec<Self> {
+    Self::discover_with(&[])
+  }
 
-    Self::new(path)
+  /// Same as [`Java::discover_all`], also scans subfolders of ``managed_dirs`` (e.g. ``MinecraftClient::get_runtimes_dir``).
+  pub fn discover_with(managed_dirs: &[PathBuf]) -> Vec<Self> {
+    discovery::find_homes(managed_dirs)
+      .into_iter()
+      .filter_map(|home| Self::new(home).ok())
+      .collect()
+  }
+
+  /// Java home folder.
+  pub fn get_path(&self) -> &Path {
+    &self.base_path
   }
 
   pub fn get_java(&self) -> Result<PathBuf> {
@@ -88,7 +118,12 @@ impl Java {
   }
 
   pub fn version(&self) -> &JavaVersion {
-    &self.version
+    &self.release.version
+  }
+
+  /// Version, vendor, architecture and kind (JRE or JDK) of the Java.
+  pub fn release(&self) -> &JavaRelease {
+    &self.release
   }
 
   /// Checks that Java client is STRICTLY equal to needed_version
@@ -96,14 +131,10 @@ impl Java {
   /// Example
   /// ```rs
   /// let client = Java::find()?;
-  /// client.is_version_equal(16)?;
+  /// client.is_version_equal(16);
   /// ```
-  pub fn is_version_equal(&self, needed_version: u8) -> Result<()> {
-    if self.version.main_version() != needed_version {
-      return Err(JavaError::new(JavaErrorKind::DifferentVersion))
-    }
-
-    Ok(())
+  pub fn is_version_equal(&self, needed_version: u8) -> bool {
+    self.release.version.main_version() == needed_version
   }
 
   /// Checks if Java client is higher or equal to needed_version
@@ -111,13 +142,9 @@ impl Java {
   /// Example
   /// ```rs
   /// let client = Java::find()?;
-  /// client.is_version_at_least(16)?;
+  /// client.is_version_at_least(16);
   /// ```
-  pub fn is_version_at_least(&self, needed_version: u8) -> Result<()> {
-    if self.version.main_version() != needed_version {
-      return Err(JavaError::new(JavaErrorKind::DifferentVersion))
-    }
-
-    Ok(())
+  pub fn is_version_at_least(&self, needed_version: u8) -> bool {
+    self.release.version.main_version() >= needed_version
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+use crate::utils::choice_by_os;
+use super::error::{JavaError, JavaErrorKind, Result};
+use super::version::JavaVersion;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JavaKind {
+  /// Runtime only.
+  Jre,
+  /// Runtime with development tools (``javac``).
+  Jdk
+}
+
+/// Information about Java installation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JavaRelease {
+  pub version: JavaVersion,
+  /// Vendor of the build (e.g. ``Eclipse Adoptium``), if it's known.
+  pub vendor: Option<String>,
+  /// Architecture of the build: ``x86_64``, ``x86``, ``aarch64``, etc.
+  pub arch: Option<String>,
+  pub kind: JavaKind
+}
+
+impl JavaRelease {
+  /// Reads ``release`` file of Java home, Java is spawned only if the file is missing or broken.
+  pub fn detect(java_base_path: &Path) -> Result<Self> {
+    let kind = match java_base_path.join("bin").join(choice_by_os("javac", "javac.exe")).is_file() {
+      true => JavaKind::Jdk,
+      false => JavaKind::Jre
+    };
+
+    let (version, vendor, arch) = match Self::read_release_file(java_base_path) {
+      Some(release) => release,
+      None => Self::read_properties(java_base_path)?
+    };
+
+    Ok(Self {
+      version,
+      vendor,
+      arch: arch.map(|arch| normalize_arch(&arch)),
+      kind
+    })
+  }
+
+  /// ``KEY="value"`` lines of ``<java home>/release``
+  fn read_release_file(java_base_path: &Path) -> Option<(JavaVersion, Option<String>, Option<String>)> {
+    let content = fs::read_to_string(java_base_path.join("release")).ok()?;
+
+    let values = content.lines()
+      .filter_map(|line| line.split_once('='))
+      .map(|(key, value)| (key.trim(), value.trim().trim_matches('"').to_string()))
+      .collect::<HashMap<&str, String>>();
+
+    let version = values.get("JAVA_VERSION")?.parse().ok()?;
+
+    Some((version, values.get("IMPLEMENTOR").cloned(), values.get("OS_ARCH").cloned()))
+  }
+
+  /// Parses output of ``java -XshowSettings:properties -version``
+  fn read_properties(java_base_path: &Path) -> Result<(JavaVersion, Option<String>, Option<String>)> {
+    let java_executable = java_base_path.join("bin")
+      .join(choice_by_os("java", "java.exe"));
+
+    let output = Command::new(java_executable)
+      .arg("-XshowSettings:properties")
+      .arg("-version")
+      .output()
+      .map_err(|e| JavaError::new_with_details(JavaErrorKind::OutputReadError, e.to_string()))?;
+
+    let output = String::from_utf8_lossy(&output.stderr)
+      .to_string();
+
+    let properties = output.lines()
+      .filter_map(|line| line.split_once(" = "))
+      .map(|(key, value)| (key.trim(), value.trim().to_string()))
+      .collect::<HashMap<&str, String>>();
+
+    // runtime version contains build number, unlike java.version
+    let version = ["java.runtime.version", "java.version"].iter()
+      .filter_map(|key| properties.get(key))
+      .find_map(|version| version.parse().ok());
+
+    let version = match version {
+      Some(version) => version,
+      // the first line looks like ``openjdk version "17.0.8" 2023-07-18``
+      None => output.lines()
+        .next()
+        .and_then(|line| line.split_whitespace().find(|s| s.starts_with('"')))
+        .ok_or(JavaError::new_with_details(JavaErrorKind::OutputReadError, String::from("Unable to get Java's version")))?
+        .parse()?
+    };
+
+    Ok((version, properties.get("java.vendor").cloned(), properties.get("os.arch").cloned()))
+  }
+}
+
+/// Java calls architectures differently on different OSes (``amd64`` and ``x86_64``)
+fn normalize_arch(arch: &str) -> String {
+  match arch {
+    "amd64" | "x86_64" | "x64" => "x86_64",
+    "aarch64" | "arm64" => "aarch64",
+    "x86" | "i386" | "i486" | "i586" | "i686" => "x86",
+    other => other
+  }.to_string()
+}
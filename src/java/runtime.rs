@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Error, ErrorKind};
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use crate::client::downloads::DownloadableObject;
+use crate::client::timestamp::Timestamp;
+
+#[cfg(feature = "download")]
+use std::{fs, io::BufReader, path::{Component, Path}};
+#[cfg(feature = "download")]
+use crate::{client::maven::artifact_path, download::{DownloadTask, Downloader}, utils::file_sha1};
+#[cfg(feature = "download")]
+use super::Java;
+
+/// URL of the Mojang's Java runtimes index (``all.json``)
+pub const RUNTIMES_URL: &str = "https://piston-meta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RuntimeAvailability {
+  pub group: u32,
+  pub progress: u32
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RuntimeVersion {
+  /// Version of Java (e.g. 17.0.8).
+  pub name: String,
+  pub released: Timestamp
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RuntimeEntry {
+  pub availability: RuntimeAvailability,
+  /// The file manifest of the runtime, see [`RuntimeManifest`].
+  pub manifest: DownloadableObject,
+  pub version: RuntimeVersion
+}
+
+/// Implementation of ``all.json`` structure: platform -> component (e.g. ``java-runtime-gamma``) -> runtimes
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(transparent)]
+pub struct RuntimeIndex {
+  pub platforms: HashMap<String, HashMap<String, Vec<RuntimeEntry>>>
+}
+
+impl RuntimeIndex {
+  pub fn new(index_file: PathBuf) -> io::Result<Self> {
+    if !index_file.is_file() {
+      return Err(Error::new(ErrorKind::NotFound, "Runtime index file is not a file"));
+    }
+
+    Ok(serde_json::from_reader(File::open(index_file)?)?)
+  }
+
+  /// Name of the current platform in the index (e.g. ``windows-x64``, ``mac-os-arm64``).
+  pub fn platform() -> Option<&'static str> {
+    let platform = match (std::env::consts::OS, std::env::consts::ARCH) {
+      ("windows", "x86_64") => "windows-x64",
+      ("windows", "x86") => "windows-x86",
+      ("windows", "aarch64") => "windows-arm64",
+      ("linux", "x86_64") => "linux",
+      ("linux", "x86") => "linux-i386",
+      ("macos", "x86_64") => "mac-os",
+      ("macos", "aarch64") => "mac-os-arm64",
+      _ => return None
+    };
+
+    Some(platform)
+  }
+
+  /// Finds the runtime of ``component`` (``ClientJavaVersion::component``) for the current platform.
+  pub fn get(&self, component: &str) -> Option<&RuntimeEntry> {
+    self.platforms.get(Self::platform()?)?
+      .get(component)?
+      .first()
+  }
+
+  /// Downloads the file manifest of ``component`` into ``runtimes_dir`` and installs the runtime
+  /// into ``runtimes_dir/<component>``.
+  ///
+  /// Example
+  /// ```rs
+  /// let runtimes_dir = config.client.get_runtimes_dir();
+  /// let java = index.install(&Downloader::default(), &client.get_java_version().component, &runtimes_dir)?;
+  /// ```
+  #[cfg(feature = "download")]
+  pub fn install(&self, downloader: &Downloader, component: &str, runtimes_dir: &Path) -> io::Result<Java> {
+    let entry = self.get(component)
+      .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("Java runtime {} is not available for this platform", component)))?;
+
+    let manifest_file = runtimes_dir.join(format!("{}.json", component));
+    downloader.download(&DownloadTask::from_object(&entry.manifest, manifest_file.clone()))?;
+
+    RuntimeManifest::new(manifest_file)?
+      .install(downloader, &runtimes_dir.join(component))
+  }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RuntimeFileDownloads {
+  pub raw: DownloadableObject,
+  /// LZMA compressed file, is preferred over ``raw`` if present.
+  pub lzma: Option<DownloadableObject>
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RuntimeFile {
+  File {
+    downloads: RuntimeFileDownloads,
+    #[serde(default)]
+    executable: bool
+  },
+  Directory,
+  /// Symbolic link, ``target`` is relative to the link's folder.
+  Link {
+    target: String
+  }
+}
+
+/// Files of a Java runtime, keys are paths separated with ``/``
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RuntimeManifest {
+  pub files: HashMap<String, RuntimeFile>
+}
+
+impl RuntimeManifest {
+  pub fn new(manifest_file: PathBuf) -> io::Result<Self> {
+    if !manifest_file.is_file() {
+      return Err(Error::new(ErrorKind::NotFound, "Runtime manifest file is not a file"));
+    }
+
+    Ok(serde_json::from_reader(File::open(manifest_file)?)?)
+  }
+
+  /// Installs the runtime into ``dir``, files that are already valid are not downloaded again.
+  #[cfg(feature = "download")]
+  pub fn install(&self, downloader: &Downloader, dir: &Path) -> io::Result<Java> {
+    // manifests come from remote hosts, nothing can be written or linked outside ``dir``
+    for (name, file) in &self.files {
+      artifact_path(name)?;
+
+      if let RuntimeFile::Link { target } = file {
+        if !is_link_inside(name, target) {
+          return Err(Error::new(ErrorKind::InvalidData, format!("Link {} points outside of the runtime: {}", name, target)));
+        }
+      }
+    }
+
+    let mut tasks = Vec::new();
+    // downloaded archive, where it's unpacked and the unpacked file
+    let mut compressed = Vec::new();
+
+    for (name, file) in &self.files {
+      let path = dir.join(artifact_path(name)?);
+
+      match file {
+        RuntimeFile::Directory => fs::create_dir_all(&path)?,
+        RuntimeFile::File { downloads, .. } => {
+          let raw = &downloads.raw;
+
+          let valid = fs::metadata(&path).is_ok_and(|metadata| metadata.len() == raw.size as u64)
+            && file_sha1(&path)?.eq_ignore_ascii_case(&raw.sha1);
+
+          if valid {
+            continue;
+          }
+
+          match &downloads.lzma {
+            Some(lzma) => {
+              let mut name = path.file_name()
+                .unwrap_or_default()
+                .to_os_string();
+              name.push(".lzma");
+
+              let archive = path.with_file_name(name);
+              tasks.push(DownloadTask::from_object(lzma, archive.clone()));
+              compressed.push((archive, path, raw));
+            },
+            None => tasks.push(DownloadTask::from_object(raw, path))
+          }
+        },
+        RuntimeFile::Link { .. } => {}
+      }
+    }
+
+    downloader.download_all(&tasks)?;
+
+    for (archive, path, raw) in compressed {
+      lzma_rs::lzma_decompress(&mut BufReader::new(File::open(&archive)?), &mut File::create(&path)?)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, format!("Unable to unpack {}: {:?}", archive.display(), e)))?;
+
+      fs::remove_file(&archive)?;
+
+      if !file_sha1(&path)?.eq_ignore_ascii_case(&raw.sha1) {
+        fs::remove_file(&path)?;
+        return Err(Error::new(ErrorKind::InvalidData, format!("Checksum mismatch of {}", path.display())));
+      }
+    }
+
+    for (name, file) in &self.files {
+      let path = dir.join(artifact_path(name)?);
+
+      match file {
+        RuntimeFile::File { executable: true, .. } => set_executable(&path)?,
+        RuntimeFile::Link { target } => create_link(target, &path)?,
+        _ => {}
+      }
+    }
+
+    // runtimes for macOS are bundles
+    let bundle_home = dir.join("jre.bundle").join("Contents").join("Home");
+
+    match bundle_home.is_dir() {
+      true => Ok(Java::new(bundle_home)?),
+      false => Ok(Java::new(dir.to_path_buf())?)
+    }
+  }
+}
+
+#[cfg(all(feature = "download", unix))]
+fn set_executable(path: &Path) -> io::Result<()> {
+  use std::os::unix::fs::PermissionsExt;
+
+  let mut permissions = fs::metadata(path)?.permissions();
+  permissions.set_mode(permissions.mode() | 0o755);
+
+  fs::set_permissions(path, permissions)
+}
+
+#[cfg(all(feature = "download", not(unix)))]
+fn set_executable(_: &Path) -> io::Result<()> {
+  Ok(())
+}
+
+/// Does relative ``target`` of link ``name`` stay in the runtime folder?
+#[cfg(feature = "download")]
+fn is_link_inside(name: &str, target: &str) -> bool {
+  if target.starts_with('/') {
+    return false;
+  }
+
+  // folders between the runtime folder and the link
+  let mut depth = name.split('/').count() - 1;
+
+  for part in target.split('/') {
+    match part {
+      "" | "." => {},
+      ".." if depth == 0 => return false,
+      ".." => depth -= 1,
+      // "C:" and other prefixes
+      part if !matches!(Path::new(part).components().next(), Some(Component::Normal(_))) => return false,
+      _ => depth += 1
+    }
+  }
+
+  true
+}
+
+#[cfg(all(feature = "download", unix))]
+fn create_link(target: &str, path: &Path) -> io::Result<()> {
+  if fs::symlink_metadata(path).is_ok() {
+    fs::remove_file(path)?;
+  }
+
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+
+  std::os::unix::fs::symlink(target, path)
+}
+
+/// Runtimes for Windows don't have links
+#[cfg(all(feature = "download", not(unix)))]
+fn create_link(_: &str, _: &Path) -> io::Result<()> {
+  Ok(())
+}
@@ -0,0 +1,65 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use super::error::{JavaError, JavaErrorKind, Result};
+use super::Java;
+
+/// Chooses Java for a version by its required major version (``ClientJavaVersion::major_version``).
+///
+/// Java is chosen in this order:
+/// 1. Java with exactly required major version, preferred one goes first;
+/// 2. Java within ``allowed`` range, preferred one goes first, then the closest to required one;
+/// 3. preferred Java, even if it doesn't fit.
+///
+/// Example
+/// ```rs
+/// let java = JavaSelector::default().select(client.get_java_version().major_version, None)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JavaSelector {
+  /// Major versions, that can be used if there is no required one.\
+  /// If ``None``, newer Java is allowed for versions requiring Java 16 and above, older versions need exactly required one.
+  pub allowed: Option<RangeInclusive<u8>>,
+  /// Folders with managed runtimes, that are scanned with the system ones (see [`Java::discover_with`]).
+  pub managed_dirs: Vec<PathBuf>
+}
+
+impl JavaSelector {
+  /// Chooses Java among ``preferred`` and every Java installation on the PC.\
+  /// The PC is not scanned if ``preferred`` has exactly required version.
+  pub fn select(&self, required: usize, preferred: Option<&Java>) -> Result<Java> {
+    let required = u8::try_from(required).unwrap_or(u8::MAX);
+
+    if let Some(java) = preferred.filter(|java| java.version().main_version() == required) {
+      return Ok(java.clone());
+    }
+
+    self.select_from(required as usize, preferred, &Java::discover_with(&self.managed_dirs))
+  }
+
+  /// Chooses Java among ``preferred`` and ``candidates``.
+  pub fn select_from(&self, required: usize, preferred: Option<&Java>, candidates: &[Java]) -> Result<Java> {
+    let required = u8::try_from(required).unwrap_or(u8::MAX);
+    let allowed = self.allowed.clone()
+      .unwrap_or(match required {
+        16.. => required..=u8::MAX,
+        _ => required..=required
+      });
+
+    let fits = |java: &&Java| allowed.contains(&java.version().main_version());
+
+    let exact = preferred.into_iter()
+      .chain(candidates)
+      .find(|java| java.version().main_version() == required);
+
+    let in_range = || preferred.filter(fits)
+      .or_else(|| candidates.iter()
+        .filter(fits)
+        .min_by_key(|java| java.version().main_version().abs_diff(required)));
+
+    exact
+      .or_else(in_range)
+      .or(preferred)
+      .cloned()
+      .ok_or(JavaError::new_with_details(JavaErrorKind::NoSuitableVersion(required), format!("Java {} is required", required)))
+  }
+}
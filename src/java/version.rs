@@ -1,66 +1,223 @@
-use std::{path::Path, process::Command};
-use crate::{java::error::{JavaError, JavaErrorKind}, utils::choice_by_os};
-use super::error::Result;
+use std::{cmp::Ordering, fmt::Display, path::Path, str::FromStr};
+use serde::{Deserialize, Serialize};
+use crate::java::error::{JavaError, JavaErrorKind};
+use super::{error::Result, release::JavaRelease};
 
+/// Version of Java, both ``1.8.0_402-b06`` and ``17.0.8+7`` schemes are supported.
+///
+/// Versions are ordered by their numbers, pre-release goes before the release (``21-ea`` < ``21``).
+///
 /// https://www.tpointtech.com/java-9-new-version-string-scheme
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
 pub struct JavaVersion {
-  major: u8,
-  minor: u8,
-  #[allow(unused)]
-  security: u8,
+  /// 8, 11, 17, 21, etc.
+  pub feature: u32,
+  pub interim: u32,
+  /// Security update (``402`` in ``1.8.0_402``).
+  pub update: u32,
+  pub patch: u32,
+  /// Pre-release identifier (e.g. ``ea``).
+  pub pre: Option<String>,
+  /// Build number (``7`` in ``17.0.8+7``, ``6`` in ``1.8.0_402-b06``).
+  pub build: Option<u32>
 }
 
-macro_rules! get_item {
-  ($i:ident, $j:expr) => {
-    *$i.get($j).ok_or(JavaError::new(JavaErrorKind::OutputReadError))?
+macro_rules! parse_error {
+  ($version:expr) => {
+    JavaError::new_with_details(JavaErrorKind::OutputReadError, format!("Unable to parse Java version {:?}", $version))
   }
 }
 
 impl JavaVersion {
-  // TODO @ its looks weird
+  /// Detects version of Java in the folder, see [`JavaRelease::detect`].
   pub fn get_verison(java_base_path: &Path) -> Result<Self> {
-    let java_executable = java_base_path.join("bin")
-      .join(choice_by_os("java", "java.exe"));
-
-    let output = Command::new(java_executable)
-      .arg("-version")
-      .output()
-      .map_err(|e| JavaError::new_with_details(JavaErrorKind::OutputReadError, e.to_string()))?;
-
-    let version = String::from_utf8_lossy(&output.stderr)
-      .to_string();
-
-    let version_line = version
-      .lines()
-      .next()
-      .ok_or(JavaError::new_with_details(JavaErrorKind::OutputReadError, String::from("No client line")))?;
-
-    let version = version_line
-      .split_whitespace()
-      .find(|s| s.starts_with('"'))
-      .ok_or(JavaError::new_with_details(JavaErrorKind::OutputReadError, String::from("Unable to get Java's version")))?
-      .trim_matches('"')
-      .split('.');
-
-    let version_parts = version.collect::<Vec<&str>>()
-      .iter()
-      .map(|e| e.parse().unwrap_or_default())
-      .collect::<Vec<u8>>();
-
-    Ok(Self {
-      major: get_item!(version_parts, 0),
-      minor: get_item!(version_parts, 1),
-      security: get_item!(version_parts, 2),
-    })
+    Ok(JavaRelease::detect(java_base_path)?.version)
   }
 
   /// 7, 8, 9, 11, 16, 17, 18, etc
   pub fn main_version(&self) -> u8 {
-    if self.major == 1 {
-      return self.minor;
+    u8::try_from(self.feature)
+      .unwrap_or(u8::MAX)
+  }
+
+  fn numbers(&self) -> [u32; 4] {
+    [self.feature, self.interim, self.update, self.patch]
+  }
+
+  /// Checks the version against comma-separated comparators: ``>=``, ``<=``, ``>``, ``<`` and ``=``.\
+  /// Missing parts of compared versions are zeros, while ``=`` (or no operator) compares only given parts.\
+  /// Invalid comparators are never satisfied.
+  ///
+  /// Example
+  /// ```rs
+  /// java.version().satisfies(">=17, <22");
+  /// java.version().satisfies("1.8");
+  /// ```
+  pub fn satisfies(&self, requirement: &str) -> bool {
+    requirement.split(',')
+      .map(str::trim)
+      .filter(|comparator| !comparator.is_empty())
+      .all(|comparator| self.matches(comparator))
+  }
+
+  fn matches(&self, comparator: &str) -> bool {
+    let (operator, version) = [">=", "<=", ">", "<", "="].iter()
+      .find_map(|operator| comparator.strip_prefix(operator).map(|version| (*operator, version.trim())))
+      .unwrap_or(("=", comparator));
+
+    let Ok(other) = version.parse::<Self>() else {
+      return false;
+    };
+
+    match operator {
+      ">=" => self >= &other,
+      "<=" => self <= &other,
+      ">" => self > &other,
+      "<" => self < &other,
+      _ => {
+        // "1.8" and "8" specify only the feature version
+        let numbers = version.split(['-', '+']).next().unwrap_or_default();
+        let depth = match numbers.strip_prefix("1.") {
+          Some(legacy) if legacy.contains('_') => 3,
+          Some(_) => 1,
+          None => numbers.split('.').count()
+        };
+
+        self.numbers()[..depth.min(4)] == other.numbers()[..depth.min(4)]
+      }
+    }
+  }
+}
+
+impl FromStr for JavaVersion {
+  type Err = JavaError;
+
+  fn from_str(s: &str) -> Result<Self> {
+    let version = s.trim().trim_matches('"');
+
+    let (version, build) = match version.split_once('+') {
+      Some((version, build)) => {
+        let digits = build.split(|c: char| !c.is_ascii_digit()).next().unwrap_or_default();
+        (version, digits.parse().ok())
+      },
+      None => (version, None)
+    };
+
+    let (numbers, suffix) = version.split_once('-')
+      .unwrap_or((version, ""));
+
+    // "1.8.0_402" has update after underscore
+    let (numbers, legacy_update) = match numbers.split_once('_') {
+      Some((numbers, update)) => (numbers, Some(update.parse().map_err(|_| parse_error!(s))?)),
+      None => (numbers, None)
+    };
+
+    let numbers = numbers.split('.')
+      .map(|number| number.parse::<u32>())
+      .collect::<std::result::Result<Vec<u32>, _>>()
+      .map_err(|_| parse_error!(s))?;
+
+    let number = |index: usize| numbers.get(index).copied().unwrap_or_default();
+
+    let mut result = match numbers.as_slice() {
+      [1, feature, ..] => Self { feature: *feature, interim: 0, update: legacy_update.unwrap_or_default(), patch: 0, pre: None, build },
+      _ => Self { feature: number(0), interim: number(1), update: number(2), patch: number(3), pre: None, build }
+    };
+
+    // "ea", "ea-b25", "b06"
+    let mut pre = Vec::new();
+
+    for part in suffix.split('-').filter(|part| !part.is_empty()) {
+      match part.strip_prefix('b').and_then(|build| build.parse().ok()) {
+        Some(build) if result.build.is_none() => result.build = Some(build),
+        _ => pre.push(part)
+      }
+    }
+
+    if !pre.is_empty() {
+      result.pre = Some(pre.join("-"));
+    }
+
+    Ok(result)
+  }
+}
+
+impl TryFrom<String> for JavaVersion {
+  type Error = JavaError;
+
+  fn try_from(value: String) -> Result<Self> {
+    value.parse()
+  }
+}
+
+impl Display for JavaVersion {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.feature <= 8 {
+      write!(f, "1.{}.0", self.feature)?;
+
+      if self.update > 0 {
+        write!(f, "_{}", self.update)?;
+      }
+
+      if let Some(pre) = &self.pre {
+        write!(f, "-{}", pre)?;
+      }
+
+      if let Some(build) = self.build {
+        write!(f, "-b{:02}", build)?;
+      }
+
+      return Ok(());
+    }
+
+    // trailing zeros are omitted: "21", "17.0.8"
+    let numbers = self.numbers();
+    let length = numbers.iter()
+      .rposition(|number| *number != 0)
+      .unwrap_or_default() + 1;
+
+    let numbers = numbers[..length].iter()
+      .map(|number| number.to_string())
+      .collect::<Vec<String>>();
+    write!(f, "{}", numbers.join("."))?;
+
+    if let Some(pre) = &self.pre {
+      write!(f, "-{}", pre)?;
     }
 
-    self.major
+    if let Some(build) = self.build {
+      write!(f, "+{}", build)?;
+    }
+
+    Ok(())
   }
-}
\ No newline at end of file
+}
+
+impl From<JavaVersion> for String {
+  fn from(value: JavaVersion) -> Self {
+    value.to_string()
+  }
+}
+
+impl PartialOrd for JavaVersion {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for JavaVersion {
+  fn cmp(&self, other: &Self) -> Ordering {
+    // release is newer than its pre-releases
+    let pre = match (&self.pre, &other.pre) {
+      (None, None) => Ordering::Equal,
+      (None, Some(_)) => Ordering::Greater,
+      (Some(_), None) => Ordering::Less,
+      (Some(a), Some(b)) => a.cmp(b)
+    };
+
+    self.numbers().cmp(&other.numbers())
+      .then(pre)
+      .then(self.build.cmp(&other.build))
+  }
+}
@@ -4,6 +4,10 @@ mod utils;
 pub mod java;
 pub mod minecraft;
 pub mod client;
+pub mod progress;
+
+#[cfg(feature = "download")]
+pub mod download;
 
 #[cfg(test)]
 mod tests;
\ No newline at end of file
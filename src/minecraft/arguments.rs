@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use crate::client::ClientFile;
-use super::configuration::MinecraftConfiguration as Configuration;
+use crate::java::JAVA_SEPARATOR;
+use super::configuration::{MinecraftConfiguration as Configuration, QuickPlay};
 
 pub type DataMap<'a> = HashMap<&'a str, String>;
 
@@ -9,39 +10,76 @@ pub fn generate_data_map<'a>(
   client_file: ClientFile,
   class_path: String
 ) -> DataMap<'a> {
+  let version_jar = config.client.get_client_jar(&client_file).to_str().unwrap().to_string();
+  let natives_dir = config.client.get_natives_dir().to_str().unwrap().to_string();
   let path = &config.client.path;
-  let user_type = serde_json::to_string(&config.session.user_type.unwrap_or_default()).unwrap();
-  let version = config.client.version;
+  let user_type = config.session.user_type.unwrap_or_default().to_string();
+  // pre-1.6 versions get the session as a single argument
+  let auth_session = format!("token:{}:{}", config.session.access_token, config.session.uuid);
+
+  let libraries_dir = config.client.get_libraries_dir().to_str().unwrap().to_string();
+  let game_dir = path.to_str().unwrap().to_string();
+  let assets_dir = config.client.get_assets_dir().to_str().unwrap().to_string();
 
   let mut map = HashMap::from([
     ("username", config.session.username.to_string()),
+    ("auth_player_name", config.session.username.to_string()),
     ("uuid", config.session.uuid.to_string()),
+    ("auth_uuid", config.session.uuid.to_string()),
     ("access_token", config.session.access_token.to_string()),
+    ("auth_access_token", config.session.access_token.to_string()),
+    ("auth_session", auth_session),
+    ("clientid", config.session.client_id.to_string()),
+    ("auth_xuid", config.session.xuid.to_string()),
+    // legacy versions expect json object here, we don't have any properties
+    ("user_properties", String::from("{}")),
     ("user_type", user_type),
     ("launcher_name", String::from("java-minecraft-launcher")),
     ("launcher_version", String::from("1.6.84-j")),
-    ("libraries_dir", path.join("libraries").to_str().unwrap().to_string()),
-    ("natives_dir", path.join("versions").join(&version).join("natives").to_str().unwrap().to_string()),
-    ("game_dir", path.to_str().unwrap().to_string()),
-    ("assets_dir", path.join("assets").to_str().unwrap().to_string()),
-    ("class_path", class_path),
+    ("libraries_dir", libraries_dir.clone()),
+    ("library_directory", libraries_dir),
+    ("natives_dir", natives_dir.clone()),
+    ("natives_directory", natives_dir),
+    ("game_dir", game_dir.clone()),
+    ("game_directory", game_dir),
+    ("assets_dir", assets_dir.clone()),
+    ("assets_root", assets_dir.clone()),
+    // legacy versions with virtual assets get another folder, see ``AssetIndexFile::get_game_assets_dir``
+    ("game_assets", assets_dir),
+    ("class_path", class_path.clone()),
+    ("classpath", class_path),
+    ("classpath_separator", JAVA_SEPARATOR.to_string()),
     ("main_class", client_file.main_class),
     ("version_name", client_file.id),
-    ("version_type", client_file.r#type),
-    ("assets_index", client_file.assets),
-    ("version_jar", path.join("versions").join(&version).join("client.jar").to_str().unwrap().to_string())
+    ("version_type", client_file.r#type.to_string()),
+    ("assets_index", client_file.assets.clone()),
+    ("assets_index_name", client_file.assets),
+    ("version_jar", version_jar)
   ]);
 
   if let Some(ip) = config.client.server {
     map.insert("server_ip", ip.to_string());
   }
 
+  if let Some(quick_play_path) = config.client.quick_play_path {
+    map.insert("quickPlayPath", quick_play_path.to_str().unwrap().to_string());
+  }
+
+  match config.client.quick_play {
+    Some(QuickPlay::Singleplayer(world)) => { map.insert("quickPlaySingleplayer", world); },
+    Some(QuickPlay::Multiplayer(server)) => { map.insert("quickPlayMultiplayer", server); },
+    Some(QuickPlay::Realms(realm)) => { map.insert("quickPlayRealms", realm); },
+    None => {}
+  }
+
   if let Some(width) = config.window.width {
     map.insert("width", width.to_string());
+    map.insert("resolution_width", width.to_string());
   }
 
   if let Some(height) = config.window.height {
     map.insert("height", height.to_string());
+    map.insert("resolution_height", height.to_string());
   }
 
   if let Some(authlib) = config.session.authlib_server {
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display};
+use std::io::Result;
+use std::path::PathBuf;
+use std::process::Stdio;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+
+#[cfg(feature = "tokio")]
+use tokio::process::{Child, Command};
+
+#[cfg(not(feature = "tokio"))]
+use std::process::{Child, Command};
+
+/// Replacement of secrets (access token, session) in arguments
+pub(crate) const REDACTED: &str = "********";
+
+/// Fully resolved command, that starts the game (see ``MinecraftLauncher::prepare``).
+///
+/// Secrets (access token, session) are redacted when it's printed (as a shell line or with ``{:?}``) or serialized,
+/// so JSON of the command can be shared, but the game can't be started from it.
+///
+/// Example
+/// ```rs
+/// let command = MinecraftLauncher::new(config).prepare()?;
+///
+/// println!("{}", command);
+/// std::fs::write("launch.json", serde_json::to_string_pretty(&command)?)?;
+///
+/// let child = command.spawn()?;
+/// ```
+#[derive(Deserialize, Clone, PartialEq, Eq)]
+pub struct LaunchCommand {
+  /// Java executable.
+  pub java: PathBuf,
+  /// Folder the game is started in (game folder).
+  pub working_dir: PathBuf,
+  /// Environment variables, that are set in addition to the launcher's ones.
+  pub env: BTreeMap<String, String>,
+  pub jvm_args: Vec<String>,
+  pub main_class: String,
+  pub game_args: Vec<String>,
+  /// Entries of the class path, that's already passed in ``jvm_args``.
+  pub classpath: Vec<PathBuf>,
+  /// Positions of arguments (in [`LaunchCommand::get_args`]) that contain secrets, with their redacted form
+  /// (e.g. ``--accessToken ********``).
+  #[serde(default)]
+  pub secrets: BTreeMap<usize, String>
+}
+
+impl LaunchCommand {
+  /// Arguments of Java: JVM arguments, main class and game arguments.
+  pub fn get_args(&self) -> Vec<String> {
+    let mut args = self.jvm_args.clone();
+    args.push(self.main_class.clone());
+    args.extend(self.game_args.iter().cloned());
+    args
+  }
+
+  /// Copy of the command, where arguments with secrets are replaced with their redacted form.
+  pub fn redacted(&self) -> Self {
+    let redact = |index: usize, arg: &String| self.secrets.get(&index)
+      .unwrap_or(arg)
+      .clone();
+
+    // main class goes between JVM and game arguments
+    let offset = self.jvm_args.len() + 1;
+
+    Self {
+      jvm_args: self.jvm_args.iter().enumerate().map(|(index, arg)| redact(index, arg)).collect(),
+      game_args: self.game_args.iter().enumerate().map(|(index, arg)| redact(offset + index, arg)).collect(),
+      ..self.clone()
+    }
+  }
+
+  /// Process of the game, stdout and stderr are piped.
+  pub fn command(&self) -> Command {
+    let mut command = Command::new(&self.java);
+
+    command.args(self.get_args())
+      .current_dir(&self.working_dir)
+      .envs(&self.env)
+      .stdout(Stdio::piped())
+      .stderr(Stdio::piped());
+
+    command
+  }
+
+  pub fn spawn(&self) -> Result<Child> {
+    self.command().spawn()
+  }
+}
+
+impl Display for LaunchCommand {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let command = self.redacted();
+
+    write!(f, "cd {} &&", quote(&command.working_dir.to_string_lossy()))?;
+
+    for (key, value) in &command.env {
+      write!(f, " {}={}", key, quote(value))?;
+    }
+
+    write!(f, " {}", quote(&command.java.to_string_lossy()))?;
+
+    for arg in command.get_args() {
+      write!(f, " {}", quote(&arg))?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Secrets are redacted, so the command can be logged
+impl Debug for LaunchCommand {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let command = self.redacted();
+
+    f.debug_struct("LaunchCommand")
+      .field("java", &command.java)
+      .field("working_dir", &command.working_dir)
+      .field("env", &command.env)
+      .field("jvm_args", &command.jvm_args)
+      .field("main_class", &command.main_class)
+      .field("game_args", &command.game_args)
+      .field("classpath", &command.classpath)
+      .finish_non_exhaustive()
+  }
+}
+
+/// Only the redacted form is written, so secrets never get into files
+impl Serialize for LaunchCommand {
+  fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    let command = self.redacted();
+    let mut state = serializer.serialize_struct("LaunchCommand", 8)?;
+
+    state.serialize_field("java", &command.java)?;
+    state.serialize_field("working_dir", &command.working_dir)?;
+    state.serialize_field("env", &command.env)?;
+    state.serialize_field("jvm_args", &command.jvm_args)?;
+    state.serialize_field("main_class", &command.main_class)?;
+    state.serialize_field("game_args", &command.game_args)?;
+    state.serialize_field("classpath", &command.classpath)?;
+    state.serialize_field("secrets", &command.secrets)?;
+    state.end()
+  }
+}
+
+/// Quotes the argument for POSIX shell, if it's needed
+fn quote(arg: &str) -> String {
+  let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=+,@%".contains(c);
+
+  if !arg.is_empty() && arg.chars().all(is_safe) {
+    return arg.to_string();
+  }
+
+  format!("'{}'", arg.replace('\'', r"'\''"))
+}
@@ -1,8 +1,10 @@
+use std::fmt::Display;
 use std::io;
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
-use crate::client::ClientFile;
-use crate::java::Java;
+use crate::client::{find_version_file, ClientFile};
+use crate::client::inheritance::ClientResolver;
+use crate::java::{selector::JavaSelector, Java};
 
 #[derive(Debug, Clone)]
 pub struct WindowConfiguration {
@@ -24,17 +26,26 @@ impl Default for WindowConfiguration {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub enum MinecraftSessionUserType {
   #[serde(rename = "mojang")]
+  #[default]
   Mojang,
   #[serde(rename = "legacy")]
-  Legacy
+  Legacy,
+  /// Microsoft account
+  #[serde(rename = "msa")]
+  Msa
 }
 
-impl Default for MinecraftSessionUserType {
-  fn default() -> Self {
-    Self::Mojang
+impl Display for MinecraftSessionUserType {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let str = match self {
+      Self::Mojang => "mojang",
+      Self::Legacy => "legacy",
+      Self::Msa => "msa"
+    };
+    write!(f, "{}", str)
   }
 }
 
@@ -60,15 +71,32 @@ impl Default for AuthLibConfiguration {
 pub struct MinecraftSession {
   /// Username of user
   pub username: String,
-  /// User type (mojang/legacy)
+  /// User type (mojang/legacy/msa)
   pub user_type: Option<MinecraftSessionUserType>,
   /// UUID
   pub uuid: String,
   /// Access Token
   pub access_token: String,
+  /// Client ID of the launcher (``${clientid}``), empty if it's unknown
+  pub client_id: String,
+  /// Xbox user ID of Microsoft accounts (``${auth_xuid}``), empty if it's unknown
+  pub xuid: String,
   /// Adds support for authlib-injector.\
   /// This field is responsible for a reference to the server that will be used for authorization, etc.
-  pub authlib_server: Option<AuthLibConfiguration>
+  pub authlib_server: Option<AuthLibConfiguration>,
+  /// Is the game going to be run in demo mode?
+  pub demo: bool
+}
+
+/// World or server the game will be connected to right after the start (1.20 and above)
+#[derive(Debug, Clone)]
+pub enum QuickPlay {
+  /// Name of the singleplayer world
+  Singleplayer(String),
+  /// Address of the server (e.g. ``mc.example.com:25565``)
+  Multiplayer(String),
+  /// Realm ID
+  Realms(String)
 }
 
 #[derive(Debug, Clone, Default)]
@@ -79,37 +107,114 @@ pub struct MinecraftClient {
   pub version: String,
   /// Server IP of the server to which the player will be connected after the game starts (does not work on versions higher than 1.20)
   pub server: Option<String>,
+  /// Quick Play target, used by versions 1.20 and above instead of ``server``
+  pub quick_play: Option<QuickPlay>,
+  /// File where the game writes Quick Play history (``--quickPlayPath``).\
+  /// Enables ``has_quick_plays_support`` feature of the rules if it's set.
+  pub quick_play_path: Option<PathBuf>,
+  /// Folder with ``libraries``, ``assets`` and ``natives``, that is shared by several game folders.\
+  /// ``path`` is used if it's ``None``. See ``StoreDeduplicator`` to move existing files into it.
+  pub store: Option<PathBuf>,
 }
 
 impl MinecraftClient {
   pub fn get_client_file(&self) -> io::Result<PathBuf> {
     Ok(
-      self.path // Client Folder
-        .join("versions") // Client Folder/versions/
-        .join(self.version.clone()) // Client Folder/versions/Forge 1.12.2/
-        .join("client.json") // Client Folder/versions/Forge 1.12.2/client.json
-        .to_path_buf()
+      // Client Folder/versions/Forge 1.12.2/client.json
+      find_version_file(&self.path.join("versions"), &self.version, "json")
     )
   }
 
-  pub fn get_client_info(&self) -> io::Result<ClientFile> {
-    let path = self.get_client_file()?;
+  /// Shared store, or the game folder if there is no store
+  fn get_store_dir(&self) -> &PathBuf {
+    self.store.as_ref()
+      .unwrap_or(&self.path)
+  }
 
-    ClientFile::new(path)
+  /// Folder with libraries of the client.
+  pub fn get_libraries_dir(&self) -> PathBuf {
+    self.get_store_dir().join("libraries")
+  }
+
+  /// Folder with asset indexes and objects.
+  pub fn get_assets_dir(&self) -> PathBuf {
+    self.get_store_dir().join("assets")
+  }
+
+  /// Folder with Java runtimes installed by [`crate::java::runtime::RuntimeIndex::install`].
+  pub fn get_runtimes_dir(&self) -> PathBuf {
+    self.get_store_dir().join("runtimes")
+  }
+
+  /// Folder where native libraries of the client are extracted.\
+  /// ``versions/<version>/natives`` of the game folder, or ``natives/<version>`` of the store.
+  pub fn get_natives_dir(&self) -> PathBuf {
+    match &self.store {
+      Some(store) => store.join("natives").join(&self.version),
+      None => self.path.join("versions").join(&self.version).join("natives")
+    }
+  }
+
+  /// Path to the jar of the client.\
+  /// Versions inheriting from another one may use jar of the parent (see ``ClientFile::jar``).
+  pub fn get_client_jar(&self, client_file: &ClientFile) -> PathBuf {
+    let version = client_file.jar.as_deref()
+      .unwrap_or(&self.version);
+
+    find_version_file(&self.path.join("versions"), version, "jar")
+  }
+
+  /// Reads client file of the version, resolving ``inheritsFrom`` chain if it's present.
+  pub fn get_client_info(&self) -> io::Result<ClientFile> {
+    ClientResolver::resolve(&self.path.join("versions"), &self.version)
   }
 }
 
-#[derive(Debug, Clone)]
+/// Format of the game's log output
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LogFormat {
+  /// log4j XML layout from client's ``logging`` configuration (as the official launcher does)
+  #[default]
+  Xml,
+  /// Plain text, client's ``logging`` configuration is ignored
+  Plain
+}
+
+/// What is done with arguments, whose placeholders (``${name}``) are unknown
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PlaceholderMode {
+  /// Launch fails with the list of unknown placeholders
+  Strict,
+  /// Argument is removed, together with the flag before it (``--clientId ${clientid}``)
+  Lenient,
+  /// Argument is passed to the game as is
+  #[default]
+  Passthrough
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct MinecraftConfiguration {
   /// Information about client that you want to run
   pub client: MinecraftClient,
-  /// By default, library will be search java on PC and use it\
-  /// But you can override it, and use custom Java.
+  /// By default, library will be search java on PC, that fits the version, and use it\
+  /// But you can prefer custom Java, it's used if it fits or if there is no fitting Java.
   pub java: Option<Java>,
+  /// How Java is chosen by version's ``javaVersion`` (see ``JavaSelector``)\
+  /// Managed runtimes folder (``MinecraftClient::get_runtimes_dir``) is always scanned
+  pub java_selector: JavaSelector,
   /// Minecraft Session information\
   /// Settings: ``Player's Username``, ``UUID``, ``Access Token``
   pub session: MinecraftSession,
   /// Minecraft window settings\
   /// Settings: ``Size of window``, ``Fullscreen mode``
   pub window: WindowConfiguration,
+  /// Format of the game's log output\
+  /// XML layout by default, if client provides log4j configuration
+  pub log_format: LogFormat,
+  /// How unknown placeholders of arguments are handled\
+  /// Arguments with them are passed as is by default
+  pub placeholders: PlaceholderMode,
+  /// Verify the installation (see ``Verifier``) before launch and refuse to launch if it's broken\
+  /// Disabled by default, since it computes SHA1 of every file
+  pub verify: bool,
 }
\ No newline at end of file
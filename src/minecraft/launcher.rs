@@ -1,13 +1,12 @@
-// use std::{io, process::{Command, Stdio}};
-use std::{io::Result, process::Stdio};
-use crate::{client::classpath::ClassPathCollector, java::Java};
-use super::{arguments::generate_data_map, configuration::MinecraftConfiguration as Configuration};
+use std::{collections::BTreeMap, io::{Error, ErrorKind, Result}};
+use crate::{client::{classpath::ClassPathCollector, natives::NativesExtractor, ClientFile}, java::Java, progress::{NoProgress, Phase, ProgressEvent, ProgressSink}};
+use super::{arguments::generate_data_map, command::{LaunchCommand, REDACTED}, configuration::MinecraftConfiguration as Configuration, verify::Verifier};
 
 #[cfg(feature = "tokio")]
-use tokio::process::{Child, Command};
+use tokio::process::Child;
 
 #[cfg(not(feature = "tokio"))]
-use std::process::{Child, Command};
+use std::process::Child;
 
 
 #[derive(Debug, Clone)]
@@ -25,21 +24,100 @@ impl MinecraftLauncher {
   }
 
   pub fn start(self) -> Result<Child> {
-    let mut client = self.0.client.get_client_info()?;
+    self.start_with_progress(&NoProgress)
+  }
+
+  /// Same as [`MinecraftLauncher::start`], reports verification, natives extraction and launch phases.
+  pub fn start_with_progress(self, progress: &dyn ProgressSink) -> Result<Child> {
+    let command = self.prepare_with_progress(progress)?;
+
+    progress.event(ProgressEvent::PhaseStarted { phase: Phase::Launch, files: 0, bytes: 0 });
+
+    let result = command.spawn();
+    progress.event(ProgressEvent::PhaseFinished { phase: Phase::Launch });
+
+    result
+  }
 
-    let java = self.0.java.clone()
-      .unwrap_or(Java::find()?);
+  /// Does everything [`MinecraftLauncher::start`] does, except starting the game.\
+  /// Natives are extracted and virtual assets are reconstructed, so the command can be spawned later.
+  pub fn prepare(&self) -> Result<LaunchCommand> {
+    self.prepare_with_progress(&NoProgress)
+  }
+
+  /// Same as [`MinecraftLauncher::prepare`], reports verification and natives extraction phases.
+  pub fn prepare_with_progress(&self, progress: &dyn ProgressSink) -> Result<LaunchCommand> {
+    let client = self.0.client.get_client_info()?;
+
+    if self.0.verify {
+      let report = Verifier::verify_with_progress(&self.0, &client, progress)?;
 
-    let class_path = ClassPathCollector::collect(&self.0, &client);
-    let arguments = generate_data_map(self.0.clone(), client.clone(), class_path);
-    let process_args = client.arguments.collect(&self.0, arguments)?;
+      if !report.is_ok() {
+        return Err(Error::new(ErrorKind::InvalidData, format!("Installation of {} is broken: {}", client.id, report)));
+      }
+    }
 
-    let child = Command::new(java.get_javaw()?)
-      .args(process_args)
-      .stdout(Stdio::piped())
-      .stderr(Stdio::piped())
-      .spawn()?;
+    let mut selector = self.0.java_selector.clone();
+    selector.managed_dirs.push(self.0.client.get_runtimes_dir());
 
-    Ok(child)
+    let java = selector.select(client.get_java_version().major_version, self.0.java.as_ref())?;
+
+    NativesExtractor::extract_with_progress(&self.0, &client, progress)?;
+
+    self.command(&client, &java)
   }
-}
\ No newline at end of file
+
+  fn command(&self, client: &ClientFile, java: &Java) -> Result<LaunchCommand> {
+    let classpath = ClassPathCollector::collect_entries(&self.0, client)?;
+    let mut arguments = generate_data_map(self.0.clone(), client.clone(), ClassPathCollector::join(&classpath));
+
+    // old versions look for assets by their names instead of hashes
+    let assets_dir = self.0.client.get_assets_dir();
+    match client.asset_index.read(&assets_dir) {
+      Ok(index) => {
+        index.reconstruct(&assets_dir, &self.0.client.path)?;
+
+        let game_assets = index.get_game_assets_dir(&assets_dir, &self.0.client.path);
+        arguments.insert("game_assets", game_assets.to_str().unwrap().to_string());
+      },
+      // the game downloads missing assets itself
+      Err(e) if e.kind() == ErrorKind::NotFound => {},
+      Err(e) => return Err(e)
+    }
+
+    let (jvm_args, game_args) = client.get_arguments(&self.0)?.collect_parts(&self.0, &arguments)?;
+
+    // arguments with hidden secrets differ from the real ones only where secrets are
+    let mut redacted_map = arguments.clone();
+    for key in ["access_token", "auth_access_token", "auth_session"] {
+      redacted_map.insert(key, REDACTED.to_string());
+    }
+
+    let (redacted_jvm, redacted_game) = client.get_arguments(&self.0)?.collect_parts(&self.0, &redacted_map)?;
+
+    let mut command = LaunchCommand {
+      java: java.get_javaw()?,
+      working_dir: self.0.client.path.clone(),
+      env: BTreeMap::new(),
+      jvm_args,
+      main_class: client.main_class.clone(),
+      game_args,
+      classpath,
+      secrets: BTreeMap::new()
+    };
+
+    let redacted = redacted_jvm.into_iter()
+      .chain([client.main_class.clone()])
+      .chain(redacted_game);
+
+    command.secrets = command.get_args()
+      .into_iter()
+      .zip(redacted)
+      .enumerate()
+      .filter(|(_, (arg, redacted))| arg != redacted)
+      .map(|(index, (_, redacted))| (index, redacted))
+      .collect();
+
+    Ok(command)
+  }
+}
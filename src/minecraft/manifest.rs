@@ -0,0 +1,189 @@
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{self, Error, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::client::find_version_file;
+use crate::client::releasetype::ReleaseType;
+use crate::client::timestamp::Timestamp;
+
+/// URL of the Mojang's version manifest
+pub const VERSION_MANIFEST_URL: &str = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LatestVersions {
+  /// ID of the latest release.
+  pub release: String,
+  /// ID of the latest snapshot.
+  pub snapshot: String
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ManifestVersion {
+  /// The ID of the version (e.g. 1.20.4).
+  pub id: String,
+  /// The type of the version.
+  pub r#type: ReleaseType,
+  /// The URL of the version's client file.
+  pub url: String,
+  /// The time when the version's client file was updated.
+  pub time: Timestamp,
+  /// The release time of the version.
+  #[serde(rename = "releaseTime")]
+  pub release_time: Timestamp,
+  /// The SHA1 of the version's client file (v2 manifest only).
+  pub sha1: Option<String>,
+  /// See ``ClientFile::compliance_level`` (v2 manifest only).
+  #[serde(rename = "complianceLevel")]
+  pub compliance_level: Option<u8>
+}
+
+/// Implementation of ``version_manifest_v2.json`` structure
+///
+/// Reference: https://minecraft.wiki/w/Version_manifest.json
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VersionManifest {
+  pub latest: LatestVersions,
+  /// Every version of the game, from the newest to the oldest.
+  pub versions: Vec<ManifestVersion>
+}
+
+impl VersionManifest {
+  pub fn new(manifest_file: PathBuf) -> io::Result<Self> {
+    if !manifest_file.is_file() {
+      return Err(Error::new(ErrorKind::NotFound, "Manifest file is not a file"));
+    }
+
+    Self::from_reader(File::open(manifest_file)?)
+  }
+
+  /// Parses the manifest from any source (e.g. body of HTTP response).
+  pub fn from_reader<R: Read>(reader: R) -> io::Result<Self> {
+    Ok(serde_json::from_reader(reader)?)
+  }
+
+  /// Finds the version by its ID.
+  pub fn get(&self, id: &str) -> Option<&ManifestVersion> {
+    self.versions.iter()
+      .find(|version| version.id == id)
+  }
+
+  pub fn latest_release(&self) -> Option<&ManifestVersion> {
+    self.get(&self.latest.release)
+  }
+
+  pub fn latest_snapshot(&self) -> Option<&ManifestVersion> {
+    self.get(&self.latest.snapshot)
+  }
+
+  /// Versions of the given type, from the newest to the oldest.
+  pub fn filter(&self, r#type: ReleaseType) -> impl Iterator<Item = &ManifestVersion> {
+    self.versions.iter()
+      .filter(move |version| version.r#type == r#type)
+  }
+}
+
+/// Fields of a client file, that are needed to list it
+#[derive(Deserialize)]
+struct LocalVersion {
+  id: String,
+  r#type: Option<ReleaseType>,
+  #[serde(rename = "releaseTime")]
+  release_time: Option<String>
+}
+
+#[derive(Debug, Clone)]
+pub struct CatalogVersion {
+  /// The ID of the version.
+  pub id: String,
+  /// The type of the version.
+  pub r#type: ReleaseType,
+  /// The release time of the version, if it's known.
+  pub release_time: Option<Timestamp>,
+  /// Information from the manifest, if the version is present there.
+  pub manifest: Option<ManifestVersion>,
+  /// Folder of the version, if it's installed.
+  pub installed: Option<PathBuf>
+}
+
+/// List of versions, that are available in the manifest and/or installed in ``versions`` folder.
+#[derive(Debug, Clone, Default)]
+pub struct VersionCatalog {
+  /// Every version, from the newest to the oldest.
+  pub versions: Vec<CatalogVersion>
+}
+
+impl VersionCatalog {
+  /// Merges versions of ``manifest`` with ones found in ``versions_dir`` (``.minecraft/versions``).
+  ///
+  /// Installed versions that aren't present in the manifest (e.g. Forge or Fabric) are listed too.
+  pub fn new(manifest: Option<&VersionManifest>, versions_dir: &Path) -> io::Result<Self> {
+    let mut versions = manifest
+      .map(|manifest| manifest.versions.iter()
+        .map(|version| CatalogVersion {
+          id: version.id.clone(),
+          r#type: version.r#type.clone(),
+          release_time: Some(version.release_time),
+          manifest: Some(version.clone()),
+          installed: None
+        })
+        .collect::<Vec<CatalogVersion>>())
+      .unwrap_or_default();
+
+    for (folder, local) in scan_versions(versions_dir)? {
+      match versions.iter_mut().find(|version| version.id == local.id) {
+        Some(version) => version.installed = Some(folder),
+        None => versions.push(CatalogVersion {
+          id: local.id,
+          r#type: local.r#type.unwrap_or(ReleaseType::Release),
+          release_time: local.release_time.and_then(|time| time.parse().ok()),
+          manifest: None,
+          installed: Some(folder)
+        })
+      }
+    }
+
+    // versions without release time go last
+    versions.sort_by_key(|version| Reverse(version.release_time));
+
+    Ok(Self { versions })
+  }
+
+  pub fn get(&self, id: &str) -> Option<&CatalogVersion> {
+    self.versions.iter()
+      .find(|version| version.id == id)
+  }
+
+  /// Versions that are installed in ``versions`` folder.
+  pub fn installed(&self) -> impl Iterator<Item = &CatalogVersion> {
+    self.versions.iter()
+      .filter(|version| version.installed.is_some())
+  }
+}
+
+/// Reads client files of every folder in ``versions_dir``, broken ones are skipped
+fn scan_versions(versions_dir: &Path) -> io::Result<Vec<(PathBuf, LocalVersion)>> {
+  let mut result = Vec::new();
+
+  if !versions_dir.is_dir() {
+    return Ok(result);
+  }
+
+  for entry in fs::read_dir(versions_dir)? {
+    let folder = entry?.path();
+
+    let Some(name) = folder.file_name().and_then(|name| name.to_str()) else {
+      continue;
+    };
+
+    let local = fs::read_to_string(find_version_file(versions_dir, name, "json"))
+      .ok()
+      .and_then(|content| serde_json::from_str::<LocalVersion>(&content).ok());
+
+    if let Some(local) = local {
+      result.push((folder, local));
+    }
+  }
+
+  Ok(result)
+}
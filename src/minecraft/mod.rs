@@ -1,3 +1,9 @@
 pub mod configuration;
 pub mod launcher;
-pub mod arguments;
\ No newline at end of file
+pub mod arguments;
+pub mod command;
+pub mod manifest;
+pub mod plan;
+pub mod store;
+pub mod verify;
+pub mod version;
\ No newline at end of file
@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::client::library::Library;
+use crate::client::maven::MavenCoordinate;
+use crate::client::rules::RuleContext;
+use crate::client::ClientFile;
+use super::configuration::MinecraftConfiguration as Configuration;
+
+/// Maven repository of Mojang, used for libraries without ``downloads`` and ``url``
+pub const LIBRARIES_URL: &str = "https://libraries.minecraft.net/";
+/// Host of asset objects
+pub const RESOURCES_URL: &str = "https://resources.download.minecraft.net/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DownloadKind {
+  ClientJar,
+  Library,
+  Natives,
+  AssetIndex,
+  AssetObject,
+  LoggingConfig
+}
+
+/// File, that is required to run a version
+#[derive(Debug, Clone)]
+pub struct DownloadEntry {
+  pub kind: DownloadKind,
+  /// Where the file should be located.
+  pub path: PathBuf,
+  /// Where the file can be downloaded from.\
+  /// ``None`` if it's unknown (e.g. libraries generated by Forge installer).
+  pub url: Option<String>,
+  /// URLs that can be used if ``url`` is unavailable.
+  pub mirrors: Vec<String>,
+  /// The SHA1 of the file, if it's known.
+  pub sha1: Option<String>,
+  /// The size of the file in bytes, if it's known.
+  pub size: Option<u64>,
+  /// Is the file already present (exists and has the right size)?
+  pub present: bool
+}
+
+impl DownloadEntry {
+  fn new(kind: DownloadKind, path: PathBuf, url: Option<String>, sha1: Option<String>, size: Option<u64>) -> Self {
+    let present = fs::metadata(&path)
+      .is_ok_and(|metadata| metadata.is_file() && size.is_none_or(|size| metadata.len() == size));
+
+    Self {
+      kind,
+      path,
+      url: url.filter(|url| !url.is_empty()),
+      mirrors: Vec::new(),
+      sha1: sha1.filter(|sha1| !sha1.is_empty()),
+      size,
+      present
+    }
+  }
+}
+
+/// Every file a version needs: client jar, libraries, natives, asset index, asset objects and logging config.
+#[derive(Debug, Clone, Default)]
+pub struct DownloadPlan {
+  pub entries: Vec<DownloadEntry>
+}
+
+impl DownloadPlan {
+  /// Computes the plan for ``client_file``.
+  ///
+  /// Asset objects are listed only if the asset index is already present,
+  /// so plan should be computed again after the index is downloaded.
+  pub fn new(config: &Configuration, client_file: &ClientFile) -> io::Result<Self> {
+    let context = RuleContext::from(config);
+    let libraries_dir = config.client.get_libraries_dir();
+    let assets_dir = config.client.get_assets_dir();
+    let mut entries = Vec::new();
+
+    if let Some(client) = &client_file.downloads.client {
+      entries.push(DownloadEntry::new(
+        DownloadKind::ClientJar,
+        config.client.get_client_jar(client_file),
+        Some(client.url.clone()),
+        Some(client.sha1.clone()),
+        Some(client.size as u64)
+      ));
+    }
+
+    for lib in client_file.libraries.iter().filter(|lib| lib.is_allowed(&context)) {
+      if !lib.is_natives_only() {
+        entries.push(library_entry(lib, &libraries_dir)?);
+      }
+
+      if let Some(entry) = natives_entry(lib, &context, &libraries_dir)? {
+        entries.push(entry);
+      }
+    }
+
+    let asset_index = &client_file.asset_index;
+    let index_entry = DownloadEntry::new(
+      DownloadKind::AssetIndex,
+      asset_index.get_path(&assets_dir),
+      Some(asset_index.url.clone()),
+      Some(asset_index.sha1.clone()),
+      Some(asset_index.size as u64)
+    );
+
+    let index_present = index_entry.present;
+    entries.push(index_entry);
+
+    // objects are listed in the index, so they can't be known before it's downloaded
+    if index_present {
+      let index = asset_index.read(&assets_dir)?;
+
+      for object in index.objects.values() {
+        let url = format!("{}{}/{}", RESOURCES_URL, object.hash.get(..2).unwrap_or_default(), object.hash);
+
+        entries.push(DownloadEntry::new(
+          DownloadKind::AssetObject,
+          object.get_path(&assets_dir),
+          Some(url),
+          Some(object.hash.clone()),
+          Some(object.size as u64)
+        ));
+      }
+    }
+
+    let logging = client_file.logging.as_ref()
+      .and_then(|logging| logging.client.as_ref());
+
+    if let Some(logging) = logging {
+      entries.push(DownloadEntry::new(
+        DownloadKind::LoggingConfig,
+        logging.get_path(&assets_dir),
+        Some(logging.file.url.clone()),
+        Some(logging.file.sha1.clone()),
+        Some(logging.file.size as u64)
+      ));
+    }
+
+    // different versions of asset index may share objects
+    let mut seen = HashSet::new();
+    entries.retain(|entry| seen.insert(entry.path.clone()));
+
+    Ok(Self { entries })
+  }
+
+  /// Total size of all files in bytes (files with unknown size are not counted).
+  pub fn total_size(&self) -> u64 {
+    self.entries.iter()
+      .filter_map(|entry| entry.size)
+      .sum()
+  }
+
+  /// Total size of files that are not present yet.
+  pub fn missing_size(&self) -> u64 {
+    self.missing()
+      .filter_map(|entry| entry.size)
+      .sum()
+  }
+
+  /// Count of files that are already present.
+  pub fn present_count(&self) -> usize {
+    self.entries.iter()
+      .filter(|entry| entry.present)
+      .count()
+  }
+
+  /// Files that should be downloaded.
+  pub fn missing(&self) -> impl Iterator<Item = &DownloadEntry> {
+    self.entries.iter()
+      .filter(|entry| !entry.present)
+  }
+}
+
+/// Libraries without downloads are hosted in maven repository from ``url`` (Forge),
+/// Mojang's one is used as a fallback
+fn maven_urls(lib: &Library, coordinate: &MavenCoordinate) -> (String, Vec<String>) {
+  let url = |base: &str| format!("{}/{}", base.trim_end_matches('/'), coordinate.to_url_path());
+  let fallback = url(LIBRARIES_URL);
+
+  match lib.url.as_deref().map(url) {
+    Some(primary) if primary != fallback => (primary, vec![fallback]),
+    _ => (fallback, Vec::new())
+  }
+}
+
+fn library_entry(lib: &Library, libraries_dir: &Path) -> io::Result<DownloadEntry> {
+  let path = libraries_dir.join(lib.to_path()?);
+  let artifact = lib.downloads.as_ref()
+    .and_then(|downloads| downloads.artifact.as_ref());
+
+  Ok(match artifact {
+    Some(artifact) => DownloadEntry::new(
+      DownloadKind::Library,
+      path,
+      Some(artifact.url.clone()),
+      Some(artifact.sha1.clone()),
+      Some(artifact.size as u64)
+    ),
+    None => {
+      let (url, mirrors) = maven_urls(lib, &lib.coordinate()?);
+
+      DownloadEntry {
+        mirrors,
+        ..DownloadEntry::new(DownloadKind::Library, path, Some(url), None, None)
+      }
+    }
+  })
+}
+
+fn natives_entry(lib: &Library, context: &RuleContext, libraries_dir: &Path) -> io::Result<Option<DownloadEntry>> {
+  let (Some(classifier), Some(path)) = (lib.get_natives_classifier(context), lib.get_natives_path(context)?) else {
+    return Ok(None);
+  };
+
+  let path = libraries_dir.join(path);
+  let artifact = lib.downloads.as_ref()
+    .and_then(|downloads| downloads.classifiers.as_ref())
+    .and_then(|classifiers| classifiers.get(&classifier));
+
+  Ok(Some(match artifact {
+    Some(artifact) => DownloadEntry::new(
+      DownloadKind::Natives,
+      path,
+      Some(artifact.url.clone()),
+      Some(artifact.sha1.clone()),
+      Some(artifact.size as u64)
+    ),
+    None => {
+      let mut coordinate = lib.coordinate()?;
+      coordinate.classifier = Some(classifier);
+
+      let (url, mirrors) = maven_urls(lib, &coordinate);
+
+      DownloadEntry {
+        mirrors,
+        ..DownloadEntry::new(DownloadKind::Natives, path, Some(url), None, None)
+      }
+    }
+  }))
+}
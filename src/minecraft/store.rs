@@ -0,0 +1,154 @@
+use std::fs;
+use std::io::{self, Error};
+use std::path::{Path, PathBuf};
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::process::Command;
+use crate::utils::{file_sha1, walk_files};
+
+/// Folders of a game directory, that can be shared
+const SHARED_FOLDERS: [&str; 2] = ["libraries", "assets"];
+
+/// Result of deduplication
+#[derive(Debug, Clone, Default)]
+pub struct DedupeReport {
+  /// Files replaced with links to the store.
+  pub linked: usize,
+  /// Files that were missing in the store and were added to it.
+  pub added: usize,
+  /// Files that differ from ones in the store, or can't be linked (e.g. the store is on another drive).
+  pub skipped: Vec<PathBuf>,
+  /// Bytes freed by linking.
+  pub saved: u64
+}
+
+/// Moves libraries and assets of game directories into a shared store.
+///
+/// Store has the same layout as ``.minecraft`` (``libraries/<maven path>``, ``assets/objects/<hash>``),
+/// so files are identified by their maven coordinates and hashes, and are compared by SHA1 before linking.
+///
+/// Example
+/// ```rs
+/// for instance in instances {
+///   StoreDeduplicator::dedupe(&instance, &store)?;
+/// }
+///
+/// let client = MinecraftClient { path: instance, store: Some(store), ..Default::default() };
+/// ```
+pub struct StoreDeduplicator;
+
+impl StoreDeduplicator {
+  /// Replaces files of ``libraries`` and ``assets`` folders of ``game_dir`` with links to the same files of ``store``.
+  ///
+  /// Hardlinks are used, reflinks (copy-on-write copies) are tried if the filesystem doesn't allow hardlinks.
+  pub fn dedupe(game_dir: &Path, store: &Path) -> io::Result<DedupeReport> {
+    let mut report = DedupeReport::default();
+
+    for folder in SHARED_FOLDERS {
+      let mut files = Vec::new();
+      walk_files(&game_dir.join(folder), &mut files)?;
+
+      for file in files {
+        let relative = file.strip_prefix(game_dir)
+          .map_err(Error::other)?;
+
+        dedupe_file(&file, &store.join(relative), &mut report)?;
+      }
+    }
+
+    Ok(report)
+  }
+}
+
+fn dedupe_file(file: &Path, stored: &Path, report: &mut DedupeReport) -> io::Result<()> {
+  if !stored.exists() {
+    if let Some(parent) = stored.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    // instance keeps its file, store gets a link to it
+    if link(file, stored).is_err() {
+      report.skipped.push(file.to_path_buf());
+      return Ok(());
+    }
+
+    report.added += 1;
+    return Ok(());
+  }
+
+  if is_same_file(file, stored)? {
+    return Ok(());
+  }
+
+  let size = fs::metadata(file)?.len();
+
+  if size != fs::metadata(stored)?.len() || file_sha1(file)? != file_sha1(stored)? {
+    report.skipped.push(file.to_path_buf());
+    return Ok(());
+  }
+
+  // the file is replaced only after the link is created, so it's never lost
+  let mut name = file.file_name()
+    .unwrap_or_default()
+    .to_os_string();
+  name.push(".dedupe");
+  let temporary = file.with_file_name(name);
+
+  if link(stored, &temporary).is_err() {
+    let _ = fs::remove_file(&temporary);
+    report.skipped.push(file.to_path_buf());
+    return Ok(());
+  }
+
+  fs::rename(temporary, file)?;
+
+  report.linked += 1;
+  report.saved += size;
+
+  Ok(())
+}
+
+/// Creates hardlink, or reflink if hardlinks are not allowed
+fn link(original: &Path, link: &Path) -> io::Result<()> {
+  if fs::hard_link(original, link).is_ok() {
+    return Ok(());
+  }
+
+  reflink(original, link)
+}
+
+#[cfg(target_os = "linux")]
+fn reflink(original: &Path, link: &Path) -> io::Result<()> {
+  run_cp(Command::new("cp").arg("--reflink=always").arg(original).arg(link))
+}
+
+#[cfg(target_os = "macos")]
+fn reflink(original: &Path, link: &Path) -> io::Result<()> {
+  run_cp(Command::new("cp").arg("-c").arg(original).arg(link))
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn run_cp(command: &mut Command) -> io::Result<()> {
+  match command.output()?.status.success() {
+    true => Ok(()),
+    false => Err(Error::other("Filesystem doesn't support reflinks"))
+  }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn reflink(_: &Path, _: &Path) -> io::Result<()> {
+  Err(Error::new(io::ErrorKind::Unsupported, "Reflinks are not supported on this OS"))
+}
+
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> io::Result<bool> {
+  use std::os::unix::fs::MetadataExt;
+
+  let (a, b) = (fs::metadata(a)?, fs::metadata(b)?);
+  Ok(a.dev() == b.dev() && a.ino() == b.ino())
+}
+
+/// Linked files are compared by content on other systems, so they are linked again
+#[cfg(not(unix))]
+fn is_same_file(_: &Path, _: &Path) -> io::Result<bool> {
+  Ok(false)
+}
@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use crate::client::ClientFile;
+use crate::progress::{NoProgress, Phase, ProgressEvent, ProgressSink};
+use crate::utils::{file_sha1, walk_files};
+use super::configuration::MinecraftConfiguration as Configuration;
+use super::plan::{DownloadKind, DownloadPlan};
+
+/// Result of installation verification
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+  /// Files that don't exist.
+  pub missing: Vec<PathBuf>,
+  /// Files with wrong size or SHA1.
+  pub corrupted: Vec<PathBuf>,
+  /// Files in ``libraries`` folder, that are not used by the version (other versions may use them).
+  pub unexpected: Vec<PathBuf>,
+  /// Count of checked files.
+  pub checked: usize
+}
+
+impl VerifyReport {
+  /// Every required file exists and is valid, unexpected files don't matter.
+  pub fn is_ok(&self) -> bool {
+    self.missing.is_empty() && self.corrupted.is_empty()
+  }
+}
+
+impl Display for VerifyReport {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} files checked, {} missing, {} corrupted", self.checked, self.missing.len(), self.corrupted.len())?;
+
+    for path in self.missing.iter().take(5) {
+      write!(f, "\nmissing: {}", path.display())?;
+    }
+
+    for path in self.corrupted.iter().take(5) {
+      write!(f, "\ncorrupted: {}", path.display())?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Checks installation of a version without network access.
+pub struct Verifier;
+
+impl Verifier {
+  /// Checks that client jar, every library and native jar allowed on the current OS,
+  /// asset index with its objects and logging config exist and have the right size and SHA1.
+  pub fn verify(config: &Configuration, client_file: &ClientFile) -> io::Result<VerifyReport> {
+    Self::verify_with_progress(config, client_file, &NoProgress)
+  }
+
+  /// Same as [`Verifier::verify`], missing and corrupted files are reported as failed.
+  pub fn verify_with_progress(config: &Configuration, client_file: &ClientFile, progress: &dyn ProgressSink) -> io::Result<VerifyReport> {
+    let plan = DownloadPlan::new(config, client_file)?;
+    let mut report = VerifyReport::default();
+
+    progress.event(ProgressEvent::PhaseStarted {
+      phase: Phase::Verify,
+      files: plan.entries.len(),
+      bytes: plan.total_size()
+    });
+
+    let result = check_entries(&plan, &mut report, progress);
+    progress.event(ProgressEvent::PhaseFinished { phase: Phase::Verify });
+    result?;
+
+    let libraries = plan.entries.iter()
+      .filter(|entry| matches!(entry.kind, DownloadKind::Library | DownloadKind::Natives))
+      .map(|entry| entry.path.clone())
+      .collect::<HashSet<PathBuf>>();
+
+    let mut files = Vec::new();
+    walk_files(&config.client.get_libraries_dir(), &mut files)?;
+
+    report.unexpected = files.into_iter()
+      .filter(|file| !libraries.contains(file))
+      .collect();
+
+    Ok(report)
+  }
+}
+
+fn check_entries(plan: &DownloadPlan, report: &mut VerifyReport, progress: &dyn ProgressSink) -> io::Result<()> {
+  for entry in &plan.entries {
+    report.checked += 1;
+    progress.event(ProgressEvent::FileStarted { path: entry.path.clone(), size: entry.size });
+
+    let Ok(metadata) = fs::metadata(&entry.path) else {
+      report.missing.push(entry.path.clone());
+      progress.event(ProgressEvent::FileFailed { path: entry.path.clone(), error: String::from("File is missing") });
+      continue;
+    };
+
+    let size_matches = entry.size.is_none_or(|size| size == metadata.len());
+    let sha1_matches = match &entry.sha1 {
+      Some(sha1) if size_matches => file_sha1(&entry.path)?.eq_ignore_ascii_case(sha1),
+      _ => size_matches
+    };
+
+    if !metadata.is_file() || !sha1_matches {
+      report.corrupted.push(entry.path.clone());
+      progress.event(ProgressEvent::FileFailed { path: entry.path.clone(), error: String::from("File is corrupted") });
+      continue;
+    }
+
+    progress.event(ProgressEvent::BytesProgressed { path: entry.path.clone(), downloaded: metadata.len(), total: entry.size });
+    progress.event(ProgressEvent::FileFinished { path: entry.path.clone() });
+  }
+
+  Ok(())
+}
@@ -0,0 +1,166 @@
+use std::cmp::Ordering;
+use std::sync::LazyLock;
+use regex::Regex;
+use crate::client::timestamp::Timestamp;
+use crate::client::ClientFile;
+use super::manifest::VersionManifest;
+
+static RELEASE_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d+(?:\.\d+)+)(?:(?:-pre| Pre-Release )(\d+)|-rc(\d+))?$").unwrap());
+static SNAPSHOT_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d{2})w(\d{2})([a-z]?)").unwrap());
+static OLD_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(rd-|c|inf-|a|b)(\d+(?:[._]\d+)*)").unwrap());
+static EMBEDDED_RELEASE_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+\.\d+(?:\.\d+)?)").unwrap());
+
+/// Stage of the release: ``1.21-pre1`` < ``1.21-rc1`` < ``1.21``
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Stage {
+  PreRelease(u32),
+  ReleaseCandidate(u32),
+  Final
+}
+
+/// Version ID split into comparable parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum VersionId {
+  /// ``rd-132211``, ``c0.30_01c``, ``inf-20100618``, ``a1.2.6`` and ``b1.7.3``, ranked in this order
+  Old { rank: u8, numbers: Vec<u32> },
+  /// ``1.20.4``, ``1.21-pre1``, ``1.14 Pre-Release 1``
+  Release { numbers: Vec<u32>, stage: Stage },
+  /// ``24w10a``
+  Snapshot { year: u32, week: u32, letter: String }
+}
+
+fn numbers(version: &str) -> Vec<u32> {
+  version.split(['.', '_'])
+    .map(|part| part.parse().unwrap_or_default())
+    .collect()
+}
+
+/// Compares numbers of versions, missing parts are zeros (``1.20`` == ``1.20.0``)
+fn compare_numbers(a: &[u32], b: &[u32]) -> Ordering {
+  let length = a.len().max(b.len());
+
+  (0..length)
+    .map(|index| a.get(index).unwrap_or(&0).cmp(b.get(index).unwrap_or(&0)))
+    .find(|ordering| ordering.is_ne())
+    .unwrap_or(Ordering::Equal)
+}
+
+impl VersionId {
+  fn parse(id: &str) -> Option<Self> {
+    if let Some(captures) = RELEASE_PATTERN.captures(id) {
+      let stage = match (captures.get(2), captures.get(3)) {
+        (Some(pre), _) => Stage::PreRelease(pre.as_str().parse().ok()?),
+        (_, Some(rc)) => Stage::ReleaseCandidate(rc.as_str().parse().ok()?),
+        _ => Stage::Final
+      };
+
+      return Some(Self::Release { numbers: numbers(&captures[1]), stage });
+    }
+
+    if let Some(captures) = SNAPSHOT_PATTERN.captures(id) {
+      return Some(Self::Snapshot {
+        year: captures[1].parse().ok()?,
+        week: captures[2].parse().ok()?,
+        letter: captures[3].to_string()
+      });
+    }
+
+    if let Some(captures) = OLD_PATTERN.captures(id) {
+      let rank = match &captures[1] {
+        "rd-" => 0,
+        "c" => 1,
+        "inf-" => 2,
+        "a" => 3,
+        _ => 4
+      };
+
+      return Some(Self::Old { rank, numbers: numbers(&captures[2]) });
+    }
+
+    // modded versions usually contain vanilla version: "1.12.2-forge-14.23.5.2860", "Forge 1.20.1"
+    let embedded = EMBEDDED_RELEASE_PATTERN.captures(id)?;
+    Some(Self::Release { numbers: numbers(&embedded[1]), stage: Stage::Final })
+  }
+
+  /// Snapshots can't be compared with releases without release time
+  fn compare(&self, other: &Self) -> Option<Ordering> {
+    match (self, other) {
+      (Self::Old { rank: a_rank, numbers: a }, Self::Old { rank: b_rank, numbers: b }) =>
+        Some(a_rank.cmp(b_rank).then_with(|| compare_numbers(a, b))),
+      (Self::Old { .. }, _) => Some(Ordering::Less),
+      (_, Self::Old { .. }) => Some(Ordering::Greater),
+      (Self::Release { numbers: a, stage: a_stage }, Self::Release { numbers: b, stage: b_stage }) =>
+        Some(compare_numbers(a, b).then(a_stage.cmp(b_stage))),
+      (Self::Snapshot { year: a_year, week: a_week, letter: a }, Self::Snapshot { year: b_year, week: b_week, letter: b }) =>
+        Some(a_year.cmp(b_year).then(a_week.cmp(b_week)).then_with(|| a.cmp(b))),
+      _ => None
+    }
+  }
+}
+
+/// Version of the game, that can be compared with other versions chronologically.
+///
+/// Release time is the source of truth, so versions created with [`MinecraftVersion::from_manifest`]
+/// or from [`ClientFile`] are always comparable.
+/// Without it ID is parsed, and snapshots can't be compared with releases (``partial_cmp`` returns ``None``).
+///
+/// Example
+/// ```rs
+/// let version = MinecraftVersion::from(&client_file);
+/// if version >= MinecraftVersion::new("1.20") { ... }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MinecraftVersion {
+  pub id: String,
+  pub release_time: Option<Timestamp>
+}
+
+impl MinecraftVersion {
+  /// Creates version without release time, only its ID is used for comparison.
+  pub fn new(id: &str) -> Self {
+    Self {
+      id: id.to_string(),
+      release_time: None
+    }
+  }
+
+  /// Creates version with release time from the manifest, if the version is present there.
+  pub fn from_manifest(manifest: &VersionManifest, id: &str) -> Self {
+    Self {
+      id: id.to_string(),
+      release_time: manifest.get(id).map(|version| version.release_time)
+    }
+  }
+}
+
+impl From<&ClientFile> for MinecraftVersion {
+  fn from(client_file: &ClientFile) -> Self {
+    Self {
+      id: client_file.id.clone(),
+      release_time: Some(client_file.release_time)
+    }
+  }
+}
+
+impl PartialEq for MinecraftVersion {
+  fn eq(&self, other: &Self) -> bool {
+    self.partial_cmp(other) == Some(Ordering::Equal)
+  }
+}
+
+impl PartialOrd for MinecraftVersion {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    if self.id == other.id {
+      return Some(Ordering::Equal);
+    }
+
+    let by_id = VersionId::parse(&self.id)
+      .zip(VersionId::parse(&other.id))
+      .and_then(|(a, b)| a.compare(&b));
+
+    match (self.release_time, other.release_time) {
+      (Some(a), Some(b)) => Some(a.cmp(&b).then(by_id.unwrap_or(Ordering::Equal))),
+      _ => by_id
+    }
+  }
+}
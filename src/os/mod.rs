@@ -1,6 +1,7 @@
 use std::process::Output;
 
 pub(crate) mod process;
+pub(crate) mod version;
 
 pub(crate) struct OsAbstraction;
 
@@ -1,39 +1,83 @@
-use std::{path::{Path, PathBuf}, process::Command};
-use crate::java::error::{JavaError, JavaErrorKind, Result};
+use std::{env, ffi::OsStr, fs, path::{Path, PathBuf}};
 use super::OsAbstraction;
 
 pub(crate) trait ProgramPathFinder {
-  fn find_path(name: &str) -> Result<PathBuf>;
+  /// Every executable named ``name`` in ``PATH`` in order of ``PATH``, symlinks are resolved and duplicates are removed
+  fn find_all_paths(name: &str) -> Vec<PathBuf>;
 }
 
 impl ProgramPathFinder for OsAbstraction {
-  #[cfg(target_os = "windows")]
-  fn find_path(name: &str) -> Result<PathBuf> {
-    let output = Command::new("where")
-      .arg(name)
-      .output()
-      .map_err(|e| JavaError::new_with_details(JavaErrorKind::OutputReadError, e.to_string()))?;
-
-    let path = OsAbstraction::read_output(&output);
-
-    Ok(
-      Path::new(path.trim()) // windows moment
-        .to_path_buf()
-    )
+  fn find_all_paths(name: &str) -> Vec<PathBuf> {
+    find_in(name, &env::var_os("PATH").unwrap_or_default())
   }
+}
+
+/// Looks for executable ``name`` in folders of ``path`` (value of ``PATH`` variable)
+pub(crate) fn find_in(name: &str, path: &OsStr) -> Vec<PathBuf> {
+  // names with folders (e.g. "./java") are not looked up in PATH
+  let dirs = match Path::new(name).components().count() > 1 {
+    true => vec![PathBuf::new()],
+    false => env::split_paths(path)
+      .filter(|dir| !dir.as_os_str().is_empty())
+      .collect()
+  };
+
+  let names = executable_names(name);
+  let mut result = Vec::new();
+
+  for dir in dirs {
+    for name in &names {
+      let candidate = dir.join(name);
+
+      if !is_executable(&candidate) {
+        continue;
+      }
+
+      if let Ok(path) = fs::canonicalize(candidate) {
+        if !result.contains(&path) {
+          result.push(path);
+        }
+      }
+    }
+  }
+
+  result
+}
 
-  #[cfg(not(target_os = "windows"))]
-  fn find_path(name: &str) -> Result<PathBuf> {
-    let output = Command::new("which")
-      .arg(name)
-      .output()
-      .map_err(|e| JavaError::new_with_details(JavaErrorKind::OutputReadError, format!("Command not found: {e}")))?;
+/// ``java`` is ``java.exe`` on Windows, extensions are taken from ``PATHEXT``
+#[cfg(target_os = "windows")]
+fn executable_names(name: &str) -> Vec<String> {
+  let pathext = env::var("PATHEXT")
+    .unwrap_or(String::from(".COM;.EXE;.BAT;.CMD"));
 
-    let path = OsAbstraction::read_output(&output);
+  let extensions = pathext.split(';')
+    .filter(|extension| !extension.is_empty())
+    .map(str::to_lowercase)
+    .collect::<Vec<String>>();
 
-    Ok(
-      Path::new(&path)
-        .to_path_buf()
-    )
+  if extensions.iter().any(|extension| name.to_lowercase().ends_with(extension)) {
+    return vec![name.to_string()];
   }
-}
\ No newline at end of file
+
+  extensions.iter()
+    .map(|extension| format!("{name}{extension}"))
+    .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn executable_names(name: &str) -> Vec<String> {
+  vec![name.to_string()]
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+  use std::os::unix::fs::PermissionsExt;
+
+  fs::metadata(path)
+    .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+  path.is_file()
+}
@@ -0,0 +1,44 @@
+use std::process::Command;
+use super::OsAbstraction;
+
+pub(crate) trait OsVersionReader {
+  /// Version of the operating system, same as ``os.version`` property of Java
+  fn os_version() -> String;
+}
+
+impl OsVersionReader for OsAbstraction {
+  #[cfg(target_os = "windows")]
+  fn os_version() -> String {
+    // Microsoft Windows [Version 10.0.19045.3693]
+    Command::new("cmd")
+      .args(["/c", "ver"])
+      .output()
+      .map(|output| OsAbstraction::read_output(&output))
+      .ok()
+      .and_then(|output| {
+        let version = output.split("Version ").nth(1)?;
+        Some(version.trim().trim_end_matches(']').to_string())
+      })
+      .unwrap_or_default()
+  }
+
+  #[cfg(target_os = "macos")]
+  fn os_version() -> String {
+    Command::new("sw_vers")
+      .arg("-productVersion")
+      .output()
+      .map(|output| OsAbstraction::read_output(&output).trim().to_string())
+      .unwrap_or_default()
+  }
+
+  #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+  fn os_version() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/osrelease")
+      .map(|version| version.trim().to_string())
+      .or_else(|_| Command::new("uname")
+        .arg("-r")
+        .output()
+        .map(|output| OsAbstraction::read_output(&output).trim().to_string()))
+      .unwrap_or_default()
+  }
+}
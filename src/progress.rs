@@ -0,0 +1,81 @@
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+/// Long-running operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+  Download,
+  Verify,
+  Natives,
+  Launch
+}
+
+#[derive(Debug, Clone)]
+pub enum ProgressEvent {
+  PhaseStarted {
+    phase: Phase,
+    /// Count of files the phase will process.
+    files: usize,
+    /// Total size of the files in bytes (files with unknown size are not counted).
+    bytes: u64
+  },
+  FileStarted {
+    path: PathBuf,
+    size: Option<u64>
+  },
+  /// ``downloaded`` is the count of bytes processed so far, it may decrease when a download is restarted.
+  BytesProgressed {
+    path: PathBuf,
+    downloaded: u64,
+    total: Option<u64>
+  },
+  FileFinished {
+    path: PathBuf
+  },
+  FileFailed {
+    path: PathBuf,
+    error: String
+  },
+  /// Sent even if the phase failed.
+  PhaseFinished {
+    phase: Phase
+  }
+}
+
+/// Receives progress of long-running operations.
+///
+/// Example
+/// ```rs
+/// let (sender, receiver) = std::sync::mpsc::channel();
+/// let downloader = Downloader { progress: Arc::new(sender), ..Default::default() };
+///
+/// std::thread::spawn(move || downloader.download_plan(&plan));
+///
+/// for event in receiver { ... }
+/// ```
+pub trait ProgressSink: Debug + Send + Sync {
+  fn event(&self, event: ProgressEvent);
+}
+
+/// Ignores every event.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+  fn event(&self, _: ProgressEvent) {}
+}
+
+/// Events are dropped after the receiver is closed.
+impl ProgressSink for Sender<ProgressEvent> {
+  fn event(&self, event: ProgressEvent) {
+    let _ = self.send(event);
+  }
+}
+
+#[cfg(feature = "tokio")]
+impl ProgressSink for tokio::sync::mpsc::UnboundedSender<ProgressEvent> {
+  fn event(&self, event: ProgressEvent) {
+    let _ = self.send(event);
+  }
+}
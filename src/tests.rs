@@ -32,11 +32,1475 @@ fn start_game() -> anyhow::Result<()> {
       path: Path::new("C:\\Users\\smxkin\\AppData\\Roaming\\ru.riverfall.launcher\\clients\\technorpg").to_path_buf(),
       version: String::from("Forge 1.12.2"),
       ..Default::default()
-    }
+    },
+    ..Default::default()
   };
 
   MinecraftLauncher::new(config)
     .start()?;
 
   Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn legacy_minecraft_arguments() -> anyhow::Result<()> {
+  use crate::client::ClientFile;
+  use crate::minecraft::arguments::generate_data_map;
+  use crate::minecraft::configuration::{MinecraftClient, MinecraftConfiguration, MinecraftSession, WindowConfiguration};
+
+  let client: ClientFile = serde_json::from_str(r#"{
+    "assetIndex": { "id": "1.12", "sha1": "1584b57c1a0b5e593fad1f5b8f78536ca640547b", "size": 143138, "totalSize": 129336389, "url": "https://launchermeta.mojang.com/v1/packages/1584b57c1a0b5e593fad1f5b8f78536ca640547b/1.12.json" },
+    "assets": "1.12",
+    "downloads": {},
+    "id": "1.12.2",
+    "javaVersion": { "component": "jre-legacy", "majorVersion": 8 },
+    "libraries": [],
+    "mainClass": "net.minecraft.client.main.Main",
+    "minecraftArguments": "--username ${auth_player_name} --version ${version_name} --gameDir ${game_directory}",
+    "minimumLauncherVersion": 18,
+    "releaseTime": "2017-09-18T08:39:46+00:00",
+    "time": "2017-09-18T08:39:46+00:00",
+    "type": "release"
+  }"#)?;
+
+  let config = MinecraftConfiguration {
+    java: None,
+    window: WindowConfiguration { fullscreen: None, ..Default::default() },
+    session: MinecraftSession {
+      username: String::from("smxkin"),
+      ..Default::default()
+    },
+    client: MinecraftClient {
+      path: std::path::PathBuf::from("minecraft"),
+      version: String::from("1.12.2"),
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let data_map = generate_data_map(config.clone(), client.clone(), String::from("client.jar"));
+  let arguments = client.get_arguments(&config)?.collect(&config, data_map)?;
+
+  assert_eq!(arguments[1..4], ["-cp", "client.jar", "net.minecraft.client.main.Main"]);
+  assert_eq!(arguments[4..6], ["--username", "smxkin"]);
+  assert_eq!(arguments.last().map(String::as_str), Some("minecraft"));
+
+  Ok(())
+}
+
+
+#[test]
+fn argument_tokens() -> anyhow::Result<()> {
+  use crate::client::ClientFile;
+  use crate::minecraft::arguments::generate_data_map;
+  use crate::minecraft::configuration::{MinecraftClient, MinecraftConfiguration, MinecraftSession, QuickPlay, WindowConfiguration};
+
+  let client: ClientFile = serde_json::from_str(r#"{
+    "arguments": {
+      "game": [
+        "--username", "${auth_player_name}",
+        { "rules": [{ "action": "allow", "features": { "is_quick_play_multiplayer": true } }], "value": ["--quickPlayMultiplayer", "${quickPlayMultiplayer}"] },
+        { "rules": [{ "action": "allow", "features": { "is_demo_user": true } }], "value": "--demo" }
+      ],
+      "jvm": [
+        { "rules": [], "value": ["-Dfoo=a;b", "-Dnatives=${natives_directory}"] },
+        "-DFabricMcEmu= net.minecraft.client.main.Main ",
+        "-cp", "${classpath}"
+      ]
+    },
+    "assetIndex": { "id": "5", "sha1": "", "size": 0, "totalSize": 0, "url": "" },
+    "assets": "5",
+    "downloads": {},
+    "id": "1.20.1",
+    "javaVersion": { "component": "java-runtime-gamma", "majorVersion": 17 },
+    "libraries": [],
+    "mainClass": "net.minecraft.client.main.Main",
+    "minimumLauncherVersion": 21,
+    "releaseTime": "2023-06-12T13:25:51+00:00",
+    "time": "2023-06-12T13:25:51+00:00",
+    "type": "release"
+  }"#)?;
+
+  let config = MinecraftConfiguration {
+    window: WindowConfiguration { width: Some(854), height: Some(480), fullscreen: None },
+    session: MinecraftSession {
+      username: String::from("smxkin"),
+      ..Default::default()
+    },
+    client: MinecraftClient {
+      path: std::path::PathBuf::from("minecraft"),
+      version: String::from("1.20.1"),
+      quick_play: Some(QuickPlay::Multiplayer(String::from("mc.example.com;25565"))),
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let data_map = generate_data_map(config.clone(), client.clone(), String::from("a.jar;b.jar"));
+  let (jvm, game) = client.get_arguments(&config)?.collect_parts(&config, &data_map)?;
+  let natives = config.client.get_natives_dir();
+
+  // arrays are separate arguments and values with ";" are kept on every OS
+  assert_eq!(jvm, [
+    String::from("-Dfoo=a;b"),
+    format!("-Dnatives={}", natives.display()),
+    String::from("-DFabricMcEmu= net.minecraft.client.main.Main "),
+    String::from("-cp"),
+    String::from("a.jar;b.jar")
+  ]);
+  assert_eq!(game, ["--username", "smxkin", "--quickPlayMultiplayer", "mc.example.com;25565", "--width", "854", "--height", "480"]);
+
+  Ok(())
+}
+
+#[test]
+fn placeholder_resolution() -> anyhow::Result<()> {
+  use crate::client::ClientFile;
+  use crate::java::JAVA_SEPARATOR;
+  use crate::minecraft::arguments::generate_data_map;
+  use crate::minecraft::configuration::{MinecraftClient, MinecraftConfiguration, MinecraftSession, MinecraftSessionUserType, PlaceholderMode, WindowConfiguration};
+
+  let client: ClientFile = serde_json::from_str(r#"{
+    "arguments": {
+      "game": [
+        "--userType", "${user_type}",
+        "--session", "${auth_session}",
+        "--clientId", "${clientid}",
+        "--launchTarget", "${launch_target}",
+        { "rules": [{ "action": "allow", "features": { "has_custom_resolution": true } }], "value": ["--width", "${resolution_width}", "--height", "${resolution_height}"] }
+      ],
+      "jvm": ["-Dseparator=${classpath_separator}", "-Dlibraries=${library_directory}", "-Dagent=${agent_path}"]
+    },
+    "assetIndex": { "id": "5", "sha1": "", "size": 0, "totalSize": 0, "url": "" },
+    "assets": "5",
+    "downloads": {},
+    "id": "1.20.1",
+    "javaVersion": { "component": "java-runtime-gamma", "majorVersion": 17 },
+    "libraries": [],
+    "mainClass": "net.minecraft.client.main.Main",
+    "minimumLauncherVersion": 21,
+    "releaseTime": "2023-06-12T13:25:51+00:00",
+    "time": "2023-06-12T13:25:51+00:00",
+    "type": "release"
+  }"#)?;
+
+  let collect = |placeholders: PlaceholderMode, height: Option<usize>| {
+    let config = MinecraftConfiguration {
+      window: WindowConfiguration { width: Some(854), height, fullscreen: None },
+      session: MinecraftSession {
+        username: String::from("smxkin"),
+        user_type: Some(MinecraftSessionUserType::Msa),
+        uuid: String::from("uuid"),
+        access_token: String::from("token"),
+        client_id: String::from("client"),
+        ..Default::default()
+      },
+      client: MinecraftClient {
+        path: std::path::PathBuf::from("minecraft"),
+        version: String::from("1.20.1"),
+        ..Default::default()
+      },
+      placeholders,
+      ..Default::default()
+    };
+
+    let data_map = generate_data_map(config.clone(), client.clone(), String::from("client.jar"));
+    client.get_arguments(&config)?.collect_parts(&config, &data_map)
+  };
+
+  // unknown arguments are dropped with their flags, window size is passed once
+  let (jvm, game) = collect(PlaceholderMode::Lenient, Some(480))?;
+  let libraries = std::path::Path::new("minecraft").join("libraries");
+  assert_eq!(jvm, [format!("-Dseparator={}", JAVA_SEPARATOR), format!("-Dlibraries={}", libraries.display())]);
+  assert_eq!(game, ["--userType", "msa", "--session", "token:token:uuid", "--clientId", "client", "--width", "854", "--height", "480"]);
+
+  let (jvm, game) = collect(PlaceholderMode::Passthrough, Some(480))?;
+  assert_eq!(jvm.last().map(String::as_str), Some("-Dagent=${agent_path}"));
+  assert_eq!(game[6..8], ["--launchTarget", "${launch_target}"]);
+
+  let error = collect(PlaceholderMode::Strict, Some(480)).unwrap_err();
+  assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+  assert!(error.to_string().ends_with("agent_path, launch_target"));
+
+  // custom resolution needs both sizes, otherwise ``${resolution_height}`` stays unknown
+  let (_, game) = collect(PlaceholderMode::Passthrough, None)?;
+  assert!(!game.iter().any(|arg| arg.contains("resolution")));
+  assert!(!game.contains(&String::from("--width")));
+
+  assert_eq!(PlaceholderMode::default(), PlaceholderMode::Passthrough);
+
+  Ok(())
+}
+
+#[test]
+fn resolve_inherited_client() -> anyhow::Result<()> {
+  use std::fs;
+  use crate::client::inheritance::ClientResolver;
+
+  let dir = tempfile::tempdir()?;
+  let versions = dir.path().join("versions");
+
+  fs::create_dir_all(versions.join("1.20.1"))?;
+  fs::create_dir_all(versions.join("fabric-loader-0.15.0-1.20.1"))?;
+  fs::write(versions.join("1.20.1").join("1.20.1.jar"), [])?;
+
+  fs::write(versions.join("1.20.1").join("1.20.1.json"), r#"{
+    "arguments": { "game": ["--username", "${auth_player_name}"], "jvm": ["-cp", "${classpath}"] },
+    "assetIndex": { "id": "5", "sha1": "", "size": 0, "totalSize": 0, "url": "" },
+    "assets": "5",
+    "downloads": {},
+    "id": "1.20.1",
+    "javaVersion": { "component": "java-runtime-gamma", "majorVersion": 17 },
+    "libraries": [
+      { "name": "org.ow2.asm:asm:9.3" },
+      { "name": "com.mojang:brigadier:1.1.8" },
+      { "name": "org.lwjgl.lwjgl:lwjgl:2.9.4-nightly-20150209", "rules": [{ "action": "allow" }, { "action": "disallow", "os": { "name": "osx" } }] },
+      { "name": "org.lwjgl.lwjgl:lwjgl:2.9.2-nightly-20140822", "rules": [{ "action": "allow", "os": { "name": "osx" } }] }
+    ],
+    "mainClass": "net.minecraft.client.main.Main",
+    "minimumLauncherVersion": 21,
+    "releaseTime": "2023-06-12T13:25:51+00:00",
+    "time": "2023-06-12T13:25:51+00:00",
+    "type": "release"
+  }"#)?;
+
+  fs::write(versions.join("fabric-loader-0.15.0-1.20.1").join("fabric-loader-0.15.0-1.20.1.json"), r#"{
+    "id": "fabric-loader-0.15.0-1.20.1",
+    "inheritsFrom": "1.20.1",
+    "arguments": { "game": [], "jvm": ["-DFabricMcEmu= net.minecraft.client.main.Main "] },
+    "libraries": [{ "name": "org.ow2.asm:asm:9.6" }, { "name": "net.fabricmc:fabric-loader:0.15.0" }],
+    "mainClass": "net.fabricmc.loader.impl.launch.knot.KnotClient",
+    "releaseTime": "2023-12-01T00:00:00+00:00",
+    "time": "2023-12-01T00:00:00+00:00",
+    "type": "release"
+  }"#)?;
+
+  let client = ClientResolver::resolve(&versions, "fabric-loader-0.15.0-1.20.1")?;
+  let libraries = client.libraries.iter()
+    .map(|library| library.name.as_str())
+    .collect::<Vec<&str>>();
+
+  assert_eq!(client.id, "fabric-loader-0.15.0-1.20.1");
+  assert_eq!(client.main_class, "net.fabricmc.loader.impl.launch.knot.KnotClient");
+  assert_eq!(client.jar.as_deref(), Some("1.20.1"));
+  assert_eq!(client.inherits_from, None);
+  // same-key libraries of the parent are kept, only the ones overridden by the child are dropped
+  assert_eq!(libraries, [
+    "org.ow2.asm:asm:9.6",
+    "net.fabricmc:fabric-loader:0.15.0",
+    "com.mojang:brigadier:1.1.8",
+    "org.lwjgl.lwjgl:lwjgl:2.9.4-nightly-20150209",
+    "org.lwjgl.lwjgl:lwjgl:2.9.2-nightly-20140822"
+  ]);
+
+  let arguments = serde_json::to_value(client.arguments)?;
+  assert_eq!(arguments["jvm"].as_array().map(Vec::len), Some(3));
+
+  Ok(())
+}
+
+
+#[test]
+fn logging_configuration_argument() -> anyhow::Result<()> {
+  use std::fs;
+  use crate::client::ClientFile;
+  use crate::minecraft::configuration::{LogFormat, MinecraftClient, MinecraftConfiguration};
+
+  let dir = tempfile::tempdir()?;
+  let log_configs = dir.path().join("assets").join("log_configs");
+  fs::create_dir_all(&log_configs)?;
+  fs::write(log_configs.join("client-1.12.xml"), "<Configuration/>")?;
+
+  let client: ClientFile = serde_json::from_str(r#"{
+    "assetIndex": { "id": "1.12", "sha1": "", "size": 0, "totalSize": 0, "url": "" },
+    "assets": "1.12",
+    "downloads": {},
+    "id": "1.12.2",
+    "javaVersion": { "component": "jre-legacy", "majorVersion": 8 },
+    "libraries": [],
+    "logging": {
+      "client": {
+        "argument": "-Dlog4j.configurationFile=${path}",
+        "file": { "id": "client-1.12.xml", "sha1": "bd65e7d2e3c237be76cfbef4c2405033d7f91521", "size": 888, "url": "" },
+        "type": "log4j2-xml"
+      }
+    },
+    "mainClass": "net.minecraft.client.main.Main",
+    "minecraftArguments": "--username ${auth_player_name}",
+    "minimumLauncherVersion": 18,
+    "releaseTime": "2017-09-18T08:39:46+00:00",
+    "time": "2017-09-18T08:39:46+00:00",
+    "type": "release"
+  }"#)?;
+
+  let mut config = MinecraftConfiguration {
+    client: MinecraftClient {
+      path: dir.path().to_path_buf(),
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let expected = format!("-Dlog4j.configurationFile={}", log_configs.join("client-1.12.xml").display());
+  let xml = serde_json::to_value(client.get_arguments(&config)?)?;
+  assert_eq!(xml["jvm"][0]["value"], expected);
+
+  config.log_format = LogFormat::Plain;
+  let plain = serde_json::to_value(client.get_arguments(&config)?)?;
+  assert_ne!(plain["jvm"][0]["value"], expected);
+
+  Ok(())
+}
+
+
+#[test]
+fn rule_evaluation() -> anyhow::Result<()> {
+  use crate::client::rules::{OsKind, Rule, RuleContext, RuleFeatures};
+
+  let mut context = RuleContext {
+    os: OsKind::Windows,
+    os_version: String::from("10.0.19045"),
+    arch: String::from("x86_64"),
+    features: RuleFeatures::default()
+  };
+
+  let rules: Vec<Rule> = serde_json::from_str(r#"[
+    { "action": "allow" },
+    { "action": "disallow", "os": { "name": "osx" } }
+  ]"#)?;
+  assert!(context.allows(&rules));
+
+  let rules: Vec<Rule> = serde_json::from_str(r#"[
+    { "action": "allow", "os": { "name": "windows", "version": "^10\\." } },
+    { "action": "disallow", "os": { "arch": "x86" } }
+  ]"#)?;
+  assert!(context.allows(&rules));
+
+  context.arch = String::from("x86");
+  assert!(!context.allows(&rules));
+
+  let rules: Vec<Rule> = serde_json::from_str(r#"[
+    { "action": "allow", "features": { "has_custom_resolution": true } }
+  ]"#)?;
+  assert!(!context.allows(&rules));
+
+  context.features.has_custom_resolution = Some(true);
+  assert!(context.allows(&rules));
+
+  Ok(())
+}
+
+
+#[test]
+fn quick_play_path_argument() -> anyhow::Result<()> {
+  use std::path::PathBuf;
+  use crate::client::ClientFile;
+  use crate::minecraft::arguments::generate_data_map;
+  use crate::minecraft::configuration::{MinecraftClient, MinecraftConfiguration, WindowConfiguration};
+
+  let client: ClientFile = serde_json::from_str(r#"{
+    "arguments": {
+      "game": [{ "rules": [{ "action": "allow", "features": { "has_quick_plays_support": true } }], "value": ["--quickPlayPath", "${quickPlayPath}"] }],
+      "jvm": []
+    },
+    "assetIndex": { "id": "5", "sha1": "", "size": 0, "totalSize": 0, "url": "" },
+    "assets": "5",
+    "downloads": {},
+    "id": "1.20.1",
+    "libraries": [],
+    "mainClass": "net.minecraft.client.main.Main",
+    "minimumLauncherVersion": 21,
+    "releaseTime": "2023-06-12T13:25:51+00:00",
+    "time": "2023-06-12T13:25:51+00:00",
+    "type": "release"
+  }"#)?;
+
+  let collect = |quick_play_path: Option<PathBuf>| {
+    let config = MinecraftConfiguration {
+      window: WindowConfiguration { fullscreen: None, ..Default::default() },
+      client: MinecraftClient { quick_play_path, ..Default::default() },
+      ..Default::default()
+    };
+
+    let data_map = generate_data_map(config.clone(), client.clone(), String::new());
+    client.get_arguments(&config)?.collect_parts(&config, &data_map)
+  };
+
+  assert!(collect(None)?.1.is_empty());
+  assert_eq!(collect(Some(PathBuf::from("quickPlay.json")))?.1, ["--quickPlayPath", "quickPlay.json"]);
+
+  Ok(())
+}
+
+#[test]
+fn maven_coordinates() -> anyhow::Result<()> {
+  use std::path::Path;
+  use crate::client::maven::MavenCoordinate;
+
+  let coordinate: MavenCoordinate = "org.lwjgl:lwjgl:3.3.1:natives-linux".parse()?;
+  assert_eq!(coordinate.to_path(), Path::new("org/lwjgl/lwjgl/3.3.1/lwjgl-3.3.1-natives-linux.jar"));
+  assert_eq!(coordinate.to_string(), "org.lwjgl:lwjgl:3.3.1:natives-linux");
+
+  let coordinate: MavenCoordinate = "de.oceanlabs.mcp:mcp_config:1.20.1-20230612.114412@zip".parse()?;
+  assert_eq!(coordinate.file_name(), "mcp_config-1.20.1-20230612.114412.zip");
+  assert_eq!(coordinate.to_string(), "de.oceanlabs.mcp:mcp_config:1.20.1-20230612.114412@zip");
+
+  assert!("net.minecraft:launchwrapper".parse::<MavenCoordinate>().is_err());
+  assert!("a:b:c:d:e".parse::<MavenCoordinate>().is_err());
+
+  let library: crate::client::library::Library = serde_json::from_str(r#"{
+    "name": "net.minecraftforge:forge:1.12.2-14.23.5.2860",
+    "downloads": { "artifact": { "path": "net/minecraftforge/forge/1.12.2-14.23.5.2860/forge-1.12.2-14.23.5.2860-universal.jar", "sha1": "", "size": 0, "url": "" } }
+  }"#)?;
+  assert_eq!(library.to_path()?.file_name().and_then(|name| name.to_str()), Some("forge-1.12.2-14.23.5.2860-universal.jar"));
+
+  // paths from remote files can't leave the libraries folder
+  for name in ["org..lwjgl:lwjgl:3.3.1", "org.lwjgl:..:3.3.1", "org.lwjgl:lwjgl:3.3.1/../..", "org.lwjgl:lwjgl:3.3.1@jar/x"] {
+    assert!(name.parse::<MavenCoordinate>().is_err(), "{} is accepted", name);
+  }
+
+  for path in ["../../evil.jar", "/etc/evil.jar", "org/./lwjgl.jar", ""] {
+    let library: crate::client::library::Library = serde_json::from_value(serde_json::json!({
+      "name": "org.lwjgl:lwjgl:3.3.1",
+      "downloads": { "artifact": { "path": path, "sha1": "", "size": 0, "url": "" } }
+    }))?;
+    assert_eq!(library.to_path().unwrap_err().kind(), std::io::ErrorKind::InvalidData, "{} is accepted", path);
+  }
+
+  Ok(())
+}
+
+
+#[test]
+fn extract_natives() -> anyhow::Result<()> {
+  use std::{fs::{self, File}, io::Write};
+  use crate::client::{natives::NativesExtractor, rules::RuleContext, ClientFile};
+  use crate::minecraft::configuration::{MinecraftClient, MinecraftConfiguration};
+
+  let dir = tempfile::tempdir()?;
+  let client: ClientFile = serde_json::from_str(r#"{
+    "assetIndex": { "id": "1.12", "sha1": "", "size": 0, "totalSize": 0, "url": "" },
+    "assets": "1.12",
+    "downloads": {},
+    "id": "1.12.2",
+    "javaVersion": { "component": "jre-legacy", "majorVersion": 8 },
+    "libraries": [{
+      "name": "org.lwjgl.lwjgl:lwjgl-platform:2.9.4-nightly-20150209",
+      "natives": { "linux": "natives-linux", "osx": "natives-osx", "windows": "natives-windows-${arch}" },
+      "extract": { "exclude": ["META-INF/"] }
+    }],
+    "mainClass": "net.minecraft.client.main.Main",
+    "minecraftArguments": "",
+    "minimumLauncherVersion": 18,
+    "releaseTime": "2017-09-18T08:39:46+00:00",
+    "time": "2017-09-18T08:39:46+00:00",
+    "type": "release"
+  }"#)?;
+
+  let config = MinecraftConfiguration {
+    client: MinecraftClient {
+      path: dir.path().to_path_buf(),
+      version: String::from("1.12.2"),
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let jar = dir.path()
+    .join("libraries")
+    .join(client.libraries[0].get_natives_path(&RuleContext::default())?.unwrap());
+  fs::create_dir_all(jar.parent().unwrap())?;
+
+  let write_jar = |content: &[u8]| -> anyhow::Result<()> {
+    let mut zip = zip::ZipWriter::new(File::create(&jar)?);
+    zip.start_file("META-INF/MANIFEST.MF", zip::write::SimpleFileOptions::default())?;
+    zip.start_file("liblwjgl.so", zip::write::SimpleFileOptions::default())?;
+    zip.write_all(content)?;
+    zip.finish()?;
+    Ok(())
+  };
+
+  write_jar(b"first")?;
+  NativesExtractor::extract(&config, &client)?;
+
+  let natives = config.client.get_natives_dir();
+  assert_eq!(fs::read(natives.join("liblwjgl.so"))?, b"first");
+  assert!(!natives.join("META-INF").exists());
+
+  // unchanged jar is not extracted again
+  fs::remove_file(natives.join("liblwjgl.so"))?;
+  NativesExtractor::extract(&config, &client)?;
+  assert!(!natives.join("liblwjgl.so").exists());
+
+  write_jar(b"second one")?;
+  NativesExtractor::extract(&config, &client)?;
+  assert_eq!(fs::read(natives.join("liblwjgl.so"))?, b"second one");
+
+  // legacy library without downloads is not a part of the classpath
+  assert!(client.libraries[0].is_natives_only());
+  let classpath = crate::client::classpath::ClassPathCollector::collect_entries(&config, &client)?;
+  assert_eq!(classpath, [config.client.get_client_jar(&client)]);
+
+  Ok(())
+}
+
+
+#[test]
+fn reconstruct_virtual_assets() -> anyhow::Result<()> {
+  use std::fs;
+  use crate::client::assetindex::AssetIndexFile;
+
+  let dir = tempfile::tempdir()?;
+  let assets = dir.path().join("assets");
+
+  let index: AssetIndexFile = serde_json::from_str(r#"{
+    "virtual": true,
+    "objects": {
+      "sounds/ambient/cave/cave1.ogg": { "hash": "de4c9d3c2ba4e4a13d6e1b13c7d7b76e50f8c0b4", "size": 5 }
+    }
+  }"#)?;
+
+  let object = &index.objects["sounds/ambient/cave/cave1.ogg"];
+  let object_path = object.get_path(&assets);
+  assert!(object_path.ends_with("objects/de/de4c9d3c2ba4e4a13d6e1b13c7d7b76e50f8c0b4"));
+
+  fs::create_dir_all(object_path.parent().unwrap())?;
+  fs::write(&object_path, "sound")?;
+
+  index.reconstruct(&assets, dir.path())?;
+
+  let game_assets = index.get_game_assets_dir(&assets, dir.path());
+  assert_eq!(game_assets, assets.join("virtual").join("legacy"));
+  assert_eq!(fs::read_to_string(game_assets.join("sounds/ambient/cave/cave1.ogg"))?, "sound");
+
+  // names of remote index can't leave the folder
+  let index: AssetIndexFile = serde_json::from_str(r#"{
+    "virtual": true,
+    "objects": {
+      "../../../escape.ogg": { "hash": "de4c9d3c2ba4e4a13d6e1b13c7d7b76e50f8c0b4", "size": 5 }
+    }
+  }"#)?;
+
+  assert_eq!(index.reconstruct(&assets, dir.path()).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+  assert!(!dir.path().join("escape.ogg").exists());
+
+  Ok(())
+}
+
+
+#[test]
+fn version_catalog() -> anyhow::Result<()> {
+  use std::fs;
+  use crate::client::releasetype::ReleaseType;
+  use crate::minecraft::manifest::{VersionCatalog, VersionManifest};
+
+  let manifest = VersionManifest::from_reader(r#"{
+    "latest": { "release": "1.20.4", "snapshot": "24w10a" },
+    "versions": [
+      { "id": "24w10a", "type": "snapshot", "url": "https://piston-meta.mojang.com/v1/packages/1/24w10a.json", "time": "2024-03-06T14:18:09+00:00", "releaseTime": "2024-03-06T14:08:38+00:00", "sha1": "1", "complianceLevel": 1 },
+      { "id": "1.20.4", "type": "release", "url": "https://piston-meta.mojang.com/v1/packages/2/1.20.4.json", "time": "2024-03-06T14:18:09+00:00", "releaseTime": "2023-12-07T12:56:20+00:00", "sha1": "2", "complianceLevel": 1 },
+      { "id": "b1.7.3", "type": "old_beta", "url": "https://piston-meta.mojang.com/v1/packages/3/b1.7.3.json", "time": "2024-03-06T14:18:09+00:00", "releaseTime": "2011-07-07T22:00:00+00:00", "sha1": "3", "complianceLevel": 0 }
+    ]
+  }"#.as_bytes())?;
+
+  assert_eq!(manifest.latest_release().map(|version| version.sha1.as_deref()), Some(Some("2")));
+  assert_eq!(manifest.latest_snapshot().map(|version| version.id.as_str()), Some("24w10a"));
+  assert_eq!(manifest.filter(ReleaseType::OldBeta).count(), 1);
+
+  let dir = tempfile::tempdir()?;
+  let forge = dir.path().join("Forge 1.20.4");
+  fs::create_dir_all(&forge)?;
+  fs::create_dir_all(dir.path().join("1.20.4"))?;
+  fs::write(forge.join("client.json"), r#"{ "id": "Forge 1.20.4", "type": "release", "releaseTime": "2023-12-08T00:00:00+00:00" }"#)?;
+  fs::write(dir.path().join("1.20.4").join("1.20.4.json"), r#"{ "id": "1.20.4", "type": "release" }"#)?;
+
+  let catalog = VersionCatalog::new(Some(&manifest), dir.path())?;
+  let ids = catalog.versions.iter()
+    .map(|version| version.id.as_str())
+    .collect::<Vec<&str>>();
+
+  assert_eq!(ids, ["24w10a", "Forge 1.20.4", "1.20.4", "b1.7.3"]);
+  assert_eq!(catalog.installed().count(), 2);
+  assert!(catalog.get("1.20.4").is_some_and(|version| version.manifest.is_some()));
+
+  Ok(())
+}
+
+
+#[test]
+fn minecraft_version_ordering() -> anyhow::Result<()> {
+  use crate::client::timestamp::Timestamp;
+  use crate::minecraft::version::MinecraftVersion;
+
+  let time: Timestamp = "2017-09-18T08:39:46+00:00".parse()?;
+  assert_eq!(time, "2017-09-18T11:39:46+0300".parse()?);
+  assert_eq!(time.to_string(), "2017-09-18T08:39:46+00:00");
+
+  // malformed offsets of remote files are errors, not panics
+  for invalid in ["2023-06-12T13:25:51+aé1", "2023-06-12T13:25:51Zaé1", "2023-06-12T13:25:51+1é", "2023-06-12T13:25:51+"] {
+    assert!(invalid.parse::<Timestamp>().is_err(), "{} is accepted", invalid);
+  }
+
+  let mut ids = ["1.21", "1.20.4", "1.21-rc1", "b1.7.3", "1.21-pre1", "a1.2.6", "1.9", "rd-132211"]
+    .map(MinecraftVersion::new);
+  ids.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+  let sorted = ids.iter()
+    .map(|version| version.id.as_str())
+    .collect::<Vec<&str>>();
+  assert_eq!(sorted, ["rd-132211", "a1.2.6", "b1.7.3", "1.9", "1.20.4", "1.21-pre1", "1.21-rc1", "1.21"]);
+
+  assert!(MinecraftVersion::new("24w10a") > MinecraftVersion::new("23w51b"));
+  assert!(MinecraftVersion::new("1.12.2-forge-14.23.5.2860") < MinecraftVersion::new("1.20"));
+  assert_eq!(MinecraftVersion::new("24w10a").partial_cmp(&MinecraftVersion::new("1.20.4")), None);
+
+  let snapshot = MinecraftVersion { id: String::from("24w10a"), release_time: Some("2024-03-06T14:08:38+00:00".parse()?) };
+  let release = MinecraftVersion { id: String::from("1.20.4"), release_time: Some("2023-12-07T12:56:20+00:00".parse()?) };
+  assert!(snapshot > release);
+
+  Ok(())
+}
+
+
+#[test]
+fn download_plan() -> anyhow::Result<()> {
+  use std::fs;
+  use crate::client::ClientFile;
+  use crate::minecraft::configuration::{MinecraftClient, MinecraftConfiguration};
+  use crate::minecraft::plan::{DownloadKind, DownloadPlan};
+
+  let dir = tempfile::tempdir()?;
+  let client: ClientFile = serde_json::from_str(r#"{
+    "assetIndex": { "id": "1.12", "sha1": "1584b57c1a0b5e593fad1f5b8f78536ca640547b", "size": 200, "totalSize": 10, "url": "https://example.com/1.12.json" },
+    "assets": "1.12",
+    "downloads": { "client": { "sha1": "0f275bc1547d01fa5f56ba34bdc87d981ee12daf", "size": 4, "url": "https://example.com/client.jar" } },
+    "id": "1.12.2",
+    "javaVersion": { "component": "jre-legacy", "majorVersion": 8 },
+    "libraries": [
+      { "name": "com.mojang:patchy:1.3.9", "downloads": { "artifact": { "path": "com/mojang/patchy/1.3.9/patchy-1.3.9.jar", "sha1": "eb8bb7b66fa0e2152b1b40b3856e82f7619439ee", "size": 23581, "url": "https://libraries.minecraft.net/com/mojang/patchy/1.3.9/patchy-1.3.9.jar" } } },
+      { "name": "net.minecraft:launchwrapper:1.12", "url": "https://maven.example.com/" },
+      { "name": "com.example:disallowed:1.0", "rules": [{ "action": "disallow" }] }
+    ],
+    "mainClass": "net.minecraft.client.main.Main",
+    "minecraftArguments": "",
+    "minimumLauncherVersion": 18,
+    "releaseTime": "2017-09-18T08:39:46+00:00",
+    "time": "2017-09-18T08:39:46+00:00",
+    "type": "release"
+  }"#)?;
+
+  let config = MinecraftConfiguration {
+    client: MinecraftClient {
+      path: dir.path().to_path_buf(),
+      version: String::from("1.12.2"),
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let plan = DownloadPlan::new(&config, &client)?;
+  assert_eq!(plan.entries.len(), 4);
+  assert_eq!(plan.present_count(), 0);
+  assert_eq!(plan.total_size(), 4 + 23581 + 200);
+
+  let launchwrapper = plan.entries.iter()
+    .find(|entry| entry.path.ends_with("launchwrapper-1.12.jar"))
+    .and_then(|entry| entry.url.as_deref());
+  assert_eq!(launchwrapper, Some("https://maven.example.com/net/minecraft/launchwrapper/1.12/launchwrapper-1.12.jar"));
+
+  let index = dir.path().join("assets").join("indexes").join("1.12.json");
+  fs::create_dir_all(index.parent().unwrap())?;
+  fs::write(&index, format!("{:<200}", r#"{ "objects": { "icons/icon_16x16.png": { "hash": "bdf48ef6b5d0d23bbb02e17d04865216179f510a", "size": 3665 } } }"#))?;
+  fs::create_dir_all(dir.path().join("versions").join("1.12.2"))?;
+  fs::write(config.client.get_client_jar(&client), "jar!")?;
+
+  let plan = DownloadPlan::new(&config, &client)?;
+  assert_eq!(plan.present_count(), 2);
+  assert_eq!(plan.missing_size(), 23581 + 3665);
+  assert!(plan.entries.iter().any(|entry| entry.kind == DownloadKind::AssetObject
+    && entry.url.as_deref() == Some("https://resources.download.minecraft.net/bd/bdf48ef6b5d0d23bbb02e17d04865216179f510a")));
+
+  Ok(())
+}
+
+
+/// Serves ``files`` over HTTP on a random local port, supports ``Range`` requests.
+/// Paths starting with ``/flaky`` fail with 500 on the first request.
+#[cfg(feature = "download")]
+fn serve(files: std::collections::HashMap<&'static str, Vec<u8>>) -> anyhow::Result<String> {
+  use std::io::{BufRead, BufReader, Write};
+  use std::net::TcpListener;
+
+  let listener = TcpListener::bind("127.0.0.1:0")?;
+  let address = format!("http://{}", listener.local_addr()?);
+
+  std::thread::spawn(move || {
+    let mut failed = std::collections::HashSet::new();
+
+    for stream in listener.incoming().flatten() {
+      let mut reader = BufReader::new(&stream);
+      let mut request = String::new();
+      let mut range = None;
+
+      let _ = reader.read_line(&mut request);
+      let path = request.split_whitespace().nth(1).unwrap_or_default().to_string();
+
+      loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).unwrap_or_default() <= 2 {
+          break;
+        }
+
+        if let Some(value) = header.to_lowercase().strip_prefix("range: bytes=") {
+          range = value.trim().trim_end_matches('-').parse::<usize>().ok();
+        }
+      }
+
+      let mut stream = &stream;
+      let response = match files.get(path.as_str()) {
+        _ if path.starts_with("/flaky") && failed.insert(path.clone()) => (500, Vec::new()),
+        Some(body) => match range {
+          Some(start) if start < body.len() => (206, body[start..].to_vec()),
+          Some(_) => (416, Vec::new()),
+          None => (200, body.clone())
+        },
+        None => (404, Vec::new())
+      };
+
+      let _ = write!(stream, "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", response.0, response.1.len());
+      let _ = stream.write_all(&response.1);
+    }
+  });
+
+  Ok(address)
+}
+
+#[cfg(feature = "download")]
+#[test]
+fn parallel_download() -> anyhow::Result<()> {
+  use std::{collections::HashMap, fs, time::Duration};
+  use crate::download::{DownloadTask, Downloader};
+  use crate::minecraft::plan::{DownloadEntry, DownloadKind, DownloadPlan};
+
+  let library = b"library content".to_vec();
+  let asset = vec![7u8; 100_000];
+  let sha1 = |data: &[u8]| sha1_smol::Sha1::from(data).digest().to_string();
+
+  let address = serve(HashMap::from([
+    ("/library.jar", library.clone()),
+    ("/flaky/asset", asset.clone())
+  ]))?;
+
+  let dir = tempfile::tempdir()?;
+  let library_path = dir.path().join("libraries").join("library.jar");
+  let asset_path = dir.path().join("assets").join("asset");
+
+  // half of the asset was downloaded before
+  fs::create_dir_all(dir.path().join("assets"))?;
+  fs::write(dir.path().join("assets").join("asset.part"), &asset[..50_000])?;
+
+  let downloader = Downloader {
+    concurrency: 2,
+    backoff: Duration::from_millis(10),
+    ..Default::default()
+  };
+
+  downloader.download_all(&[
+    DownloadTask { url: format!("{}/library.jar", address), mirrors: Vec::new(), path: library_path.clone(), sha1: Some(sha1(&library)), size: Some(library.len() as u64) },
+    DownloadTask { url: format!("{}/flaky/asset", address), mirrors: Vec::new(), path: asset_path.clone(), sha1: Some(sha1(&asset)), size: Some(asset.len() as u64) }
+  ])?;
+
+  assert_eq!(fs::read(&library_path)?, library);
+  assert_eq!(fs::read(&asset_path)?, asset);
+  assert!(!dir.path().join("assets").join("asset.part").exists());
+
+  let corrupted = downloader.download(&DownloadTask {
+    url: format!("{}/library.jar", address),
+    mirrors: Vec::new(),
+    path: dir.path().join("corrupted.jar"),
+    sha1: Some(sha1(b"something else")),
+    size: None
+  });
+  assert!(corrupted.is_err());
+  assert!(!dir.path().join("corrupted.jar").exists());
+
+  // files without URL are reported after the others are downloaded
+  let entry = |name: &str, url: Option<String>| DownloadEntry {
+    kind: DownloadKind::Library,
+    path: dir.path().join("plan").join(name),
+    url,
+    mirrors: Vec::new(),
+    sha1: None,
+    size: None,
+    present: false
+  };
+
+  let plan = DownloadPlan { entries: vec![entry("forge.jar", None), entry("library.jar", Some(format!("{}/library.jar", address)))] };
+  let error = downloader.download_plan(&plan).unwrap_err();
+  assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+  assert!(error.to_string().contains("forge.jar"));
+  assert_eq!(fs::read(dir.path().join("plan").join("library.jar"))?, library);
+
+  Ok(())
+}
+
+#[cfg(feature = "download")]
+#[test]
+fn download_mirrors() -> anyhow::Result<()> {
+  use std::{collections::HashMap, fs, net::TcpListener, sync::Arc, time::Duration};
+  use crate::download::{DownloadTask, Downloader, mirror::{MirrorConfiguration, MirrorRule, UrlRewriter}};
+
+  let bmclapi = MirrorConfiguration::bmclapi();
+  assert_eq!(bmclapi.rewrite("https://libraries.minecraft.net/a/b/1/b-1.jar"), [
+    "https://bmclapi2.bangbang93.com/maven/a/b/1/b-1.jar",
+    "https://libraries.minecraft.net/a/b/1/b-1.jar"
+  ]);
+  assert_eq!(bmclapi.rewrite("https://example.com/file"), ["https://example.com/file"]);
+
+  let library = b"library content".to_vec();
+  let address = serve(HashMap::from([("/maven/a/b/1/b-1.jar", library.clone())]))?;
+
+  // nothing listens on the port after the listener is dropped
+  let dead = format!("http://{}", TcpListener::bind("127.0.0.1:0")?.local_addr()?);
+
+  let downloader = Downloader {
+    retries: 0,
+    backoff: Duration::from_millis(10),
+    rewriter: Arc::new(MirrorConfiguration {
+      rules: vec![MirrorRule {
+        from: String::from("https://libraries.minecraft.net/"),
+        to: vec![format!("{}/", dead), format!("{}/maven/", address)],
+        keep_original: false
+      }]
+    }),
+    ..Default::default()
+  };
+
+  let dir = tempfile::tempdir()?;
+  let path = dir.path().join("b-1.jar");
+
+  // Forge's maven is unknown to the rewriter, so the fallback URL is used
+  downloader.download(&DownloadTask {
+    url: format!("{}/forge/a/b/1/b-1.jar", dead),
+    mirrors: vec![String::from("https://libraries.minecraft.net/a/b/1/b-1.jar")],
+    path: path.clone(),
+    sha1: None,
+    size: Some(library.len() as u64)
+  })?;
+
+  assert_eq!(fs::read(&path)?, library);
+
+  Ok(())
+}
+
+#[test]
+fn verify_installation() -> anyhow::Result<()> {
+  use std::fs;
+  use crate::minecraft::configuration::{MinecraftClient, MinecraftConfiguration};
+  use crate::minecraft::launcher::MinecraftLauncher;
+  use crate::minecraft::verify::Verifier;
+
+  let dir = tempfile::tempdir()?;
+  let version = dir.path().join("versions").join("1.12.2");
+  let libraries = dir.path().join("libraries");
+  fs::create_dir_all(&version)?;
+  fs::create_dir_all(libraries.join("com/mojang/patchy/1.3.9"))?;
+
+  fs::write(version.join("client.json"), r#"{
+    "assetIndex": { "id": "1.12", "sha1": "", "size": 0, "totalSize": 0, "url": "" },
+    "assets": "1.12",
+    "downloads": { "client": { "sha1": "0f275bc1547d01fa5f56ba34bdc87d981ee12daf", "size": 4, "url": "" } },
+    "id": "1.12.2",
+    "javaVersion": { "component": "jre-legacy", "majorVersion": 8 },
+    "libraries": [
+      { "name": "com.mojang:patchy:1.3.9", "downloads": { "artifact": { "path": "com/mojang/patchy/1.3.9/patchy-1.3.9.jar", "sha1": "a9993e364706816aba3e25717850c26c9cd0d89d", "size": 3, "url": "" } } },
+      { "name": "com.mojang:realms:1.10.22" }
+    ],
+    "mainClass": "net.minecraft.client.main.Main",
+    "minecraftArguments": "",
+    "minimumLauncherVersion": 18,
+    "releaseTime": "2017-09-18T08:39:46+00:00",
+    "time": "2017-09-18T08:39:46+00:00",
+    "type": "release"
+  }"#)?;
+
+  fs::write(version.join("client.jar"), "jar?")?;
+  fs::write(libraries.join("com/mojang/patchy/1.3.9/patchy-1.3.9.jar"), "abc")?;
+  fs::write(libraries.join("leftover.jar"), "")?;
+
+  let config = MinecraftConfiguration {
+    client: MinecraftClient {
+      path: dir.path().to_path_buf(),
+      version: String::from("1.12.2"),
+      ..Default::default()
+    },
+    verify: true,
+    ..Default::default()
+  };
+
+  let report = Verifier::verify(&config, &config.client.get_client_info()?)?;
+
+  assert!(!report.is_ok());
+  assert_eq!(report.corrupted, [version.join("client.jar")]);
+  assert_eq!(report.missing.len(), 2); // realms and asset index
+  assert_eq!(report.unexpected, [libraries.join("leftover.jar")]);
+
+  let error = MinecraftLauncher::new(config).start().err();
+  assert_eq!(error.map(|e| e.kind()), Some(std::io::ErrorKind::InvalidData));
+
+  Ok(())
+}
+
+#[test]
+fn progress_events() -> anyhow::Result<()> {
+  use std::{fs, sync::mpsc};
+  use crate::minecraft::configuration::{MinecraftClient, MinecraftConfiguration};
+  use crate::minecraft::verify::Verifier;
+  use crate::progress::{Phase, ProgressEvent};
+
+  let dir = tempfile::tempdir()?;
+  let version = dir.path().join("versions").join("1.12.2");
+  fs::create_dir_all(&version)?;
+
+  fs::write(version.join("client.json"), r#"{
+    "assetIndex": { "id": "1.12", "sha1": "", "size": 0, "totalSize": 0, "url": "" },
+    "assets": "1.12",
+    "downloads": { "client": { "sha1": "a9993e364706816aba3e25717850c26c9cd0d89d", "size": 3, "url": "" } },
+    "id": "1.12.2",
+    "javaVersion": { "component": "jre-legacy", "majorVersion": 8 },
+    "libraries": [{ "name": "com.mojang:realms:1.10.22" }],
+    "mainClass": "net.minecraft.client.main.Main",
+    "minecraftArguments": "",
+    "minimumLauncherVersion": 18,
+    "releaseTime": "2017-09-18T08:39:46+00:00",
+    "time": "2017-09-18T08:39:46+00:00",
+    "type": "release"
+  }"#)?;
+  fs::write(version.join("client.jar"), "abc")?;
+
+  let config = MinecraftConfiguration {
+    client: MinecraftClient {
+      path: dir.path().to_path_buf(),
+      version: String::from("1.12.2"),
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let (sender, receiver) = mpsc::channel();
+  Verifier::verify_with_progress(&config, &config.client.get_client_info()?, &sender)?;
+  drop(sender);
+
+  let events = receiver.into_iter().collect::<Vec<ProgressEvent>>();
+
+  assert!(matches!(events.first(), Some(ProgressEvent::PhaseStarted { phase: Phase::Verify, files: 3, bytes: 3 })));
+  assert!(matches!(events.last(), Some(ProgressEvent::PhaseFinished { phase: Phase::Verify })));
+
+  let finished = events.iter()
+    .filter_map(|event| match event {
+      ProgressEvent::FileFinished { path } => Some(path.clone()),
+      _ => None
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(finished, [version.join("client.jar")]);
+
+  // realms and asset index
+  let failed = events.iter()
+    .filter(|event| matches!(event, ProgressEvent::FileFailed { .. }))
+    .count();
+  assert_eq!(failed, 2);
+
+  Ok(())
+}
+
+#[test]
+fn shared_store() -> anyhow::Result<()> {
+  use std::fs;
+  use crate::minecraft::configuration::MinecraftClient;
+  use crate::minecraft::store::StoreDeduplicator;
+
+  let dir = tempfile::tempdir()?;
+  let store = dir.path().join("store");
+  let library = "libraries/org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3.jar";
+
+  for (instance, content) in [("first", "lwjgl"), ("second", "lwjgl"), ("third", "patched lwjgl")] {
+    let path = dir.path().join(instance).join(library);
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(path, content)?;
+  }
+
+  let first = StoreDeduplicator::dedupe(&dir.path().join("first"), &store)?;
+  assert_eq!((first.added, first.linked), (1, 0));
+
+  let second = StoreDeduplicator::dedupe(&dir.path().join("second"), &store)?;
+  assert_eq!((second.added, second.linked, second.saved), (0, 1, 5));
+
+  // files with other content are not replaced
+  let third = StoreDeduplicator::dedupe(&dir.path().join("third"), &store)?;
+  assert_eq!(third.skipped, [dir.path().join("third").join(library)]);
+  assert_eq!(fs::read_to_string(dir.path().join("third").join(library))?, "patched lwjgl");
+
+  let again = StoreDeduplicator::dedupe(&dir.path().join("second"), &store)?;
+  assert_eq!(again.added, 0);
+  assert!(again.skipped.is_empty());
+  assert_eq!(fs::read_to_string(dir.path().join("second").join(library))?, "lwjgl");
+
+  let client = MinecraftClient {
+    path: dir.path().join("second"),
+    version: String::from("1.20.4"),
+    store: Some(store.clone()),
+    ..Default::default()
+  };
+
+  assert_eq!(client.get_libraries_dir(), store.join("libraries"));
+  assert_eq!(client.get_assets_dir(), store.join("assets"));
+  assert_eq!(client.get_natives_dir(), store.join("natives").join("1.20.4"));
+
+  Ok(())
+}
+
+#[cfg(all(feature = "download", unix))]
+#[test]
+fn install_java_runtime() -> anyhow::Result<()> {
+  use std::{collections::HashMap, fs, os::unix::fs::PermissionsExt, sync::Arc};
+  use crate::download::{Downloader, mirror::{MirrorConfiguration, MirrorRule}};
+  use crate::java::runtime::{RuntimeIndex, RuntimeManifest};
+
+  let sha1 = |data: &[u8]| sha1_smol::Sha1::from(data).digest().to_string();
+
+  let java = b"#!/bin/sh\necho 'openjdk version \"17.0.8\" 2023-07-18' >&2\n".to_vec();
+  let mut compressed = Vec::new();
+  lzma_rs::lzma_compress(&mut &java[..], &mut compressed)?;
+  let release = b"JAVA_VERSION=\"17.0.8\"\n".to_vec();
+
+  let manifest = format!(r#"{{ "files": {{
+    "bin": {{ "type": "directory" }},
+    "bin/java": {{ "type": "file", "executable": true, "downloads": {{
+      "lzma": {{ "sha1": "{}", "size": {}, "url": "https://mojang.invalid/java.lzma" }},
+      "raw": {{ "sha1": "{}", "size": {}, "url": "https://mojang.invalid/java" }}
+    }} }},
+    "release": {{ "type": "file", "executable": false, "downloads": {{
+      "raw": {{ "sha1": "{}", "size": {}, "url": "https://mojang.invalid/release" }}
+    }} }},
+    "lib/current": {{ "type": "link", "target": "../release" }}
+  }} }}"#, sha1(&compressed), compressed.len(), sha1(&java), java.len(), sha1(&release), release.len());
+
+  let index: RuntimeIndex = serde_json::from_str(&format!(r#"{{ "{}": {{ "java-runtime-gamma": [{{
+    "availability": {{ "group": 1, "progress": 100 }},
+    "manifest": {{ "sha1": "{}", "size": {}, "url": "https://mojang.invalid/manifest.json" }},
+    "version": {{ "name": "17.0.8", "released": "2023-07-18T00:00:00+00:00" }}
+  }}] }} }}"#, RuntimeIndex::platform().unwrap(), sha1(manifest.as_bytes()), manifest.len()))?;
+
+  let address = serve(HashMap::from([
+    ("/manifest.json", manifest.into_bytes()),
+    ("/java.lzma", compressed),
+    ("/release", release.clone())
+  ]))?;
+
+  // manifests point to Mojang's hosts, so they are redirected to the local server
+  let downloader = Downloader {
+    rewriter: Arc::new(MirrorConfiguration {
+      rules: vec![MirrorRule {
+        from: String::from("https://mojang.invalid/"),
+        to: vec![format!("{}/", address)],
+        keep_original: false
+      }]
+    }),
+    ..Default::default()
+  };
+
+  let dir = tempfile::tempdir()?;
+  let runtime = index.install(&downloader, "java-runtime-gamma", dir.path())?;
+  let home = dir.path().join("java-runtime-gamma");
+
+  assert_eq!(runtime.version().main_version(), 17);
+  assert_eq!(fs::read(home.join("bin").join("java"))?, java);
+  assert_ne!(fs::metadata(home.join("bin").join("java"))?.permissions().mode() & 0o111, 0);
+  assert_eq!(fs::read(home.join("lib").join("current"))?, release);
+  assert!(!home.join("bin").join("java.lzma").exists());
+
+  assert!(index.install(&downloader, "jre-legacy", dir.path()).is_err());
+
+  // nothing is written or linked outside of the runtime folder
+  let target = dir.path().join("escaping");
+  for files in [
+    r#"{ "../../evil": { "type": "directory" } }"#,
+    r#"{ "/tmp/evil": { "type": "directory" } }"#,
+    r#"{ "bin": { "type": "directory" }, "lib/current": { "type": "link", "target": "../../evil" } }"#,
+    r#"{ "bin": { "type": "directory" }, "lib/current": { "type": "link", "target": "/etc/passwd" } }"#
+  ] {
+    let manifest: RuntimeManifest = serde_json::from_str(&format!(r#"{{ "files": {} }}"#, files))?;
+    assert_eq!(manifest.install(&downloader, &target).unwrap_err().kind(), std::io::ErrorKind::InvalidData, "{} is accepted", files);
+    assert!(!target.exists());
+  }
+
+  Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn discover_java() -> anyhow::Result<()> {
+  use std::{fs, os::unix::fs::{symlink, PermissionsExt}};
+  use crate::java::Java;
+
+  let dir = tempfile::tempdir()?;
+  let runtimes = dir.path().join("runtimes");
+
+  for (name, version) in [("java-runtime-gamma", "17.0.8"), ("jre-legacy", "1.8.0_51")] {
+    let java = runtimes.join(name).join("bin").join("java");
+    fs::create_dir_all(java.parent().unwrap())?;
+    fs::write(&java, format!("#!/bin/sh\necho 'java version \"{}\"' >&2\n", version))?;
+    fs::set_permissions(&java, fs::Permissions::from_mode(0o755))?;
+  }
+
+  // the same installation under another name
+  symlink(runtimes.join("java-runtime-gamma"), runtimes.join("current"))?;
+  fs::create_dir_all(runtimes.join("broken"))?;
+
+  let runtimes = fs::canonicalize(runtimes)?;
+  let mut found = Java::discover_with(std::slice::from_ref(&runtimes))
+    .into_iter()
+    .filter(|java| java.get_path().starts_with(&runtimes))
+    .map(|java| (java.get_path().to_path_buf(), java.version().main_version()))
+    .collect::<Vec<_>>();
+  found.sort();
+
+  assert_eq!(found, [(runtimes.join("java-runtime-gamma"), 17), (runtimes.join("jre-legacy"), 8)]);
+
+  Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn java_selection() -> anyhow::Result<()> {
+  use std::{fs, os::unix::fs::PermissionsExt};
+  use crate::client::javaversion::ClientJavaVersion;
+  use crate::java::{error::JavaErrorKind, selector::JavaSelector, Java};
+
+  let dir = tempfile::tempdir()?;
+  let fake_java = |version: &str| -> anyhow::Result<Java> {
+    let java = dir.path().join(version).join("bin").join("java");
+    fs::create_dir_all(java.parent().unwrap())?;
+    fs::write(&java, format!("#!/bin/sh\necho 'java version \"{}\"' >&2\n", version))?;
+    fs::set_permissions(&java, fs::Permissions::from_mode(0o755))?;
+    Ok(Java::new(dir.path().join(version))?)
+  };
+
+  let (java8, java17, java21) = (fake_java("1.8.0_51")?, fake_java("17.0.8")?, fake_java("21.0.3")?);
+  let installed = [java21.clone(), java8.clone(), java17.clone()];
+  let selector = JavaSelector::default();
+  let major = |java: Java| java.version().main_version();
+
+  assert_eq!(major(selector.select_from(17, None, &installed)?), 17);
+  // exact version goes before preferred one
+  assert_eq!(major(selector.select_from(17, Some(&java21), &installed)?), 17);
+  assert_eq!(major(selector.select_from(17, Some(&java21), std::slice::from_ref(&java8))?), 21);
+  assert_eq!(major(selector.select_from(16, None, &installed)?), 17);
+  // versions requiring Java 8 don't run on newer ones
+  let error = selector.select_from(8, None, &[java17.clone(), java21.clone()]).unwrap_err();
+  assert!(matches!(error.kind(), JavaErrorKind::NoSuitableVersion(8)));
+  // and preferred Java is the last resort
+  assert_eq!(major(selector.select_from(8, Some(&java21), std::slice::from_ref(&java17))?), 21);
+
+  let selector = JavaSelector { allowed: Some(8..=17), ..Default::default() };
+  assert_eq!(major(selector.select_from(8, None, &[java21, java17])?), 17);
+
+  // client files without javaVersion
+  let infer = |time: &str| -> anyhow::Result<usize> { Ok(ClientJavaVersion::infer(time.parse()?).major_version) };
+  assert_eq!(infer("2017-09-18T08:39:46+00:00")?, 8);
+  assert_eq!(infer("2021-06-08T11:00:40+00:00")?, 16);
+  assert_eq!(infer("2023-12-07T12:56:20+00:00")?, 17);
+  assert_eq!(infer("2024-06-13T08:24:03+00:00")?, 21);
+
+  Ok(())
+}
+
+#[test]
+fn java_version_comparison() -> anyhow::Result<()> {
+  use crate::java::version::JavaVersion;
+
+  let parse = |version: &str| version.parse::<JavaVersion>();
+
+  let legacy = parse("1.8.0_402-b06")?;
+  assert_eq!((legacy.feature, legacy.update, legacy.build, legacy.main_version()), (8, 402, Some(6), 8));
+  assert_eq!(legacy.to_string(), "1.8.0_402-b06");
+
+  let modern = parse("\"17.0.8.1+1\"")?;
+  assert_eq!((modern.feature, modern.interim, modern.update, modern.patch, modern.build), (17, 0, 8, 1, Some(1)));
+  assert_eq!(parse("21")?.to_string(), "21");
+  assert_eq!(parse("21.0.3+9-LTS")?.to_string(), "21.0.3+9");
+
+  let early_access = parse("21-ea")?;
+  assert_eq!(early_access.pre.as_deref(), Some("ea"));
+  assert!(early_access < parse("21")?);
+  assert!(parse("1.8.0_402")? < parse("11.0.2")?);
+  assert!(parse("17.0.10")? > parse("17.0.9")?);
+  assert!(parse("abc").is_err());
+
+  assert!(modern.satisfies(">=17, <22"));
+  assert!(!legacy.satisfies(">=17, <22"));
+  assert!(legacy.satisfies("1.8") && legacy.satisfies("=8") && !legacy.satisfies("8.1"));
+  assert!(!modern.satisfies("<=17"));
+  assert!(!modern.satisfies(">=seventeen"));
+
+  assert_eq!(serde_json::to_string(&legacy)?, "\"1.8.0_402-b06\"");
+  assert_eq!(serde_json::from_str::<JavaVersion>("\"17.0.8+7\"")?, parse("17.0.8+7")?);
+
+  Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn detect_java_release() -> anyhow::Result<()> {
+  use std::{fs, os::unix::fs::PermissionsExt};
+  use crate::java::{release::JavaKind, Java};
+
+  let dir = tempfile::tempdir()?;
+  let fake_java = |name: &str, stderr: &str| -> anyhow::Result<std::path::PathBuf> {
+    let home = dir.path().join(name);
+    let java = home.join("bin").join("java");
+    fs::create_dir_all(java.parent().unwrap())?;
+    fs::write(&java, format!("#!/bin/sh\ncat >&2 <<'EOF'\n{}\nEOF\n", stderr))?;
+    fs::set_permissions(&java, fs::Permissions::from_mode(0o755))?;
+    Ok(home)
+  };
+
+  // release file is preferred over the output of Java
+  let jdk = fake_java("jdk-21", "openjdk version \"11.0.2\" 2019-01-15")?;
+  fs::write(jdk.join("bin").join("javac"), "")?;
+  fs::write(jdk.join("release"), "IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"21\"\nOS_ARCH=\"amd64\"\n")?;
+
+  let java = Java::new(jdk)?;
+  assert_eq!(java.version().to_string(), "21");
+  assert_eq!(java.release().vendor.as_deref(), Some("Eclipse Adoptium"));
+  assert_eq!(java.release().arch.as_deref(), Some("x86_64"));
+  assert_eq!(java.release().kind, JavaKind::Jdk);
+
+  let early_access = fake_java("jdk-23", "")?;
+  fs::write(early_access.join("release"), "JAVA_VERSION=\"23-ea\"\n")?;
+  assert_eq!(Java::new(early_access)?.version().pre.as_deref(), Some("ea"));
+
+  // without release file properties are read
+  let jre = fake_java("jre-8", "Property settings:\n    java.runtime.version = 1.8.0_402-b06\n    java.vendor = Temurin\n    os.arch = aarch64\n\nopenjdk version \"1.8.0_402\"")?;
+  let java = Java::new(jre)?;
+  assert_eq!((java.version().main_version(), java.version().update, java.version().build), (8, 402, Some(6)));
+  assert_eq!(java.release().vendor.as_deref(), Some("Temurin"));
+  assert_eq!(java.release().arch.as_deref(), Some("aarch64"));
+  assert_eq!(java.release().kind, JavaKind::Jre);
+
+  Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn path_lookup() -> anyhow::Result<()> {
+  use std::{env, fs, os::unix::fs::{symlink, PermissionsExt}};
+  use crate::os::process::find_in;
+
+  let temp = tempfile::tempdir()?;
+  let dir = fs::canonicalize(temp.path())?;
+
+  for folder in ["not-executable", "bin", "linked", "jdk/bin", "usr-bin"] {
+    fs::create_dir_all(dir.join(folder))?;
+  }
+
+  fs::write(dir.join("not-executable/tool"), "")?;
+  fs::write(dir.join("bin/tool"), "#!/bin/sh\n")?;
+  fs::set_permissions(dir.join("bin/tool"), fs::Permissions::from_mode(0o755))?;
+  fs::write(dir.join("jdk/bin/tool"), "#!/bin/sh\n")?;
+  fs::set_permissions(dir.join("jdk/bin/tool"), fs::Permissions::from_mode(0o755))?;
+
+  // the same executable twice and a symlink to another one
+  symlink(dir.join("bin/tool"), dir.join("linked/tool"))?;
+  symlink(dir.join("jdk/bin/tool"), dir.join("usr-bin/tool"))?;
+
+  let path = env::join_paths(["not-executable", "bin", "linked", "missing", "usr-bin"].map(|folder| dir.join(folder)))?;
+
+  assert_eq!(find_in("tool", &path), [dir.join("bin/tool"), dir.join("jdk/bin/tool")]);
+  assert!(find_in("other-tool", &path).is_empty());
+  assert_eq!(find_in(dir.join("usr-bin/tool").to_str().unwrap(), &path), [dir.join("jdk/bin/tool")]);
+
+  Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn install_java_archive() -> anyhow::Result<()> {
+  use std::{fs::{self, File}, io::Write};
+  use flate2::{write::GzEncoder, Compression};
+  use zip::{write::SimpleFileOptions, ZipWriter};
+  use crate::java::install::{Checksum, JavaArchive, JavaInstaller};
+  use crate::utils::file_sha256;
+
+  let temp = tempfile::tempdir()?;
+  let java_script = "#!/bin/sh\necho 'java version \"21.0.2\"' >&2\n";
+  let release = "IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"21.0.2\"\nOS_ARCH=\"aarch64\"\n";
+
+  let write_tar = |name: &str, files: &[(&str, &str, u32)]| -> anyhow::Result<std::path::PathBuf> {
+    let path = temp.path().join(name);
+    let mut tar = tar::Builder::new(GzEncoder::new(File::create(&path)?, Compression::default()));
+
+    for (path, content, mode) in files {
+      let mut header = tar::Header::new_gnu();
+      header.set_size(content.len() as u64);
+      header.set_mode(*mode);
+      header.set_cksum();
+      tar.append_data(&mut header, path, content.as_bytes())?;
+    }
+
+    tar.into_inner()?.finish()?;
+    Ok(path)
+  };
+
+  // Linux layout with a top-level folder
+  let tar_path = write_tar("OpenJDK21U-jre_x64_linux.tar.gz", &[("jdk-21.0.2+13-jre/bin/java", java_script, 0o755), ("jdk-21.0.2+13-jre/release", release, 0o644)])?;
+
+  // macOS layout without a top-level folder
+  let zip_path = temp.path().join("OpenJDK17U-jre_aarch64_mac.zip");
+  let mut zip = ZipWriter::new(File::create(&zip_path)?);
+  zip.start_file("Contents/Home/bin/java", SimpleFileOptions::default().unix_permissions(0o755))?;
+  zip.write_all(java_script.as_bytes())?;
+  zip.start_file("Contents/Home/release", SimpleFileOptions::default())?;
+  zip.write_all(release.replace("21.0.2", "17.0.10").as_bytes())?;
+  zip.finish()?;
+
+  let runtimes = temp.path().join("runtimes");
+  let installer = JavaInstaller::new(runtimes.clone());
+
+  let mismatch = installer.install(&JavaArchive { path: tar_path.clone(), checksum: Some(Checksum::Sha1(String::from("0000"))) });
+  assert_eq!(mismatch.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+  assert!(installer.list()?.is_empty());
+
+  let checksum = Checksum::Sha256(file_sha256(&tar_path)?.to_uppercase());
+  let java21 = installer.install(&JavaArchive { path: tar_path, checksum: Some(checksum) })?;
+  assert_eq!(java21.get_path(), runtimes.join("jdk-21.0.2+13-jre"));
+  assert_eq!(java21.version().to_string(), "21.0.2");
+  assert!(java21.get_java()?.is_file());
+
+  let java17 = installer.install(&JavaArchive { path: zip_path, checksum: None })?;
+  assert_eq!(java17.get_path(), runtimes.join("OpenJDK17U-jre_aarch64_mac/Contents/Home"));
+  assert_eq!(java17.version().main_version(), 17);
+
+  let installed = installer.list()?;
+  assert_eq!(installed.iter().map(|java| java.id.as_str()).collect::<Vec<_>>(), ["jdk-21.0.2+13-jre", "OpenJDK17U-jre_aarch64_mac"]);
+  assert_eq!(installed[0].vendor.as_deref(), Some("Eclipse Adoptium"));
+
+  // broken build of the same folder doesn't replace the installed one
+  let broken = write_tar("OpenJDK21U-broken.tar.gz", &[("jdk-21.0.2+13-jre/bin/javac", java_script, 0o755)])?;
+  assert!(installer.install(&JavaArchive { path: broken, checksum: None }).is_err());
+  assert!(java21.get_java()?.is_file());
+  assert_eq!(installer.list()?.len(), 2);
+
+  installer.uninstall("jdk-21.0.2+13-jre")?;
+  assert!(!runtimes.join("jdk-21.0.2+13-jre").exists());
+  assert_eq!(installer.list()?.len(), 1);
+  assert_eq!(installer.uninstall("jdk-21.0.2+13-jre").unwrap_err().kind(), std::io::ErrorKind::NotFound);
+
+  // only installations are left in the folder
+  assert_eq!(fs::read_dir(&runtimes)?.count(), 2);
+
+  Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn prepare_launch_command() -> anyhow::Result<()> {
+  use std::{fs, os::unix::fs::PermissionsExt};
+  use crate::java::Java;
+  use crate::minecraft::{command::LaunchCommand, launcher::MinecraftLauncher};
+  use crate::minecraft::configuration::{MinecraftClient, MinecraftConfiguration, MinecraftSession, WindowConfiguration};
+
+  let temp = tempfile::tempdir()?;
+  let game_dir = fs::canonicalize(temp.path())?.join("game dir");
+  let versions = game_dir.join("versions").join("1.20.1");
+  fs::create_dir_all(&versions)?;
+  fs::write(versions.join("1.20.1.jar"), [])?;
+  fs::write(versions.join("1.20.1.json"), r#"{
+    "arguments": {
+      "game": ["--username", "${auth_player_name}", "--accessToken", "${auth_access_token}"],
+      "jvm": ["-Djava.library.path=${natives_directory}", "-cp", "${classpath}"]
+    },
+    "assetIndex": { "id": "5", "sha1": "", "size": 0, "totalSize": 0, "url": "" },
+    "assets": "5",
+    "downloads": {},
+    "id": "1.20.1",
+    "javaVersion": { "component": "java-runtime-gamma", "majorVersion": 17 },
+    "libraries": [{ "name": "org.ow2.asm:asm:9.3" }],
+    "mainClass": "net.minecraft.client.main.Main",
+    "minimumLauncherVersion": 21,
+    "releaseTime": "2023-06-12T13:25:51+00:00",
+    "time": "2023-06-12T13:25:51+00:00",
+    "type": "release"
+  }"#)?;
+
+  // prints its folder and arguments
+  let java_home = temp.path().join("jdk-17");
+  fs::create_dir_all(java_home.join("bin"))?;
+  fs::write(java_home.join("bin/java"), "#!/bin/sh\npwd\necho \"$@\"\n")?;
+  fs::set_permissions(java_home.join("bin/java"), fs::Permissions::from_mode(0o755))?;
+  fs::write(java_home.join("release"), "JAVA_VERSION=\"17.0.8\"\n")?;
+
+  let config = MinecraftConfiguration {
+    java: Some(Java::new(java_home.clone())?),
+    window: WindowConfiguration { fullscreen: None, ..Default::default() },
+    session: MinecraftSession {
+      username: String::from("smxkin"),
+      access_token: String::from("secret-token"),
+      ..Default::default()
+    },
+    client: MinecraftClient {
+      path: game_dir.clone(),
+      version: String::from("1.20.1"),
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  // missing asset index is skipped, broken one is an error
+  fs::create_dir_all(game_dir.join("assets/indexes"))?;
+  fs::write(game_dir.join("assets/indexes/5.json"), "{")?;
+  assert!(MinecraftLauncher::new(config.clone()).prepare().is_err());
+  fs::remove_file(game_dir.join("assets/indexes/5.json"))?;
+
+  let command = MinecraftLauncher::new(config.clone()).prepare()?;
+  let library = game_dir.join("libraries/org/ow2/asm/asm/9.3/asm-9.3.jar");
+  let natives = game_dir.join("versions/1.20.1/natives");
+
+  assert_eq!(command.java, java_home.join("bin/java"));
+  assert_eq!(command.working_dir, game_dir);
+  assert_eq!(command.classpath, [library.clone(), versions.join("1.20.1.jar")]);
+  assert_eq!(command.jvm_args, [format!("-Djava.library.path={}", natives.display()), String::from("-cp"), format!("{}:{}", library.display(), versions.join("1.20.1.jar").display())]);
+  assert_eq!(command.main_class, "net.minecraft.client.main.Main");
+  assert_eq!(command.game_args, ["--username", "smxkin", "--accessToken", "secret-token"]);
+
+  let line = command.to_string();
+  assert!(line.starts_with(&format!("cd '{}' && ", game_dir.display())));
+  assert!(line.ends_with("net.minecraft.client.main.Main --username smxkin --accessToken '********'"));
+  assert!(!line.contains("secret-token"));
+  assert!(!format!("{:?}", command).contains("secret-token"));
+
+  assert_eq!(command.secrets, [(7, String::from("********"))].into());
+
+  // secrets are never serialized, deserialized command stays redacted
+  let json = serde_json::to_string(&command)?;
+  assert!(!json.contains("secret-token"));
+  let restored: LaunchCommand = serde_json::from_str(&json)?;
+  assert_eq!(restored.secrets, command.secrets);
+  assert_eq!(restored.to_string(), line);
+  assert_eq!(restored.redacted(), command.redacted());
+
+  // only whole arguments are redacted, short tokens don't touch other ones
+  let mut offline = config.clone();
+  offline.session.access_token = String::from("0");
+  let line = MinecraftLauncher::new(offline).prepare()?.to_string();
+  assert!(line.contains(&versions.join("1.20.1.jar").display().to_string()));
+  assert!(line.ends_with("--accessToken '********'"));
+
+  #[cfg(not(feature = "tokio"))]
+  {
+    let output = String::from_utf8(command.spawn()?.wait_with_output()?.stdout)?;
+    assert_eq!(output.lines().collect::<Vec<_>>(), [game_dir.display().to_string(), command.get_args().join(" ")]);
+  }
+
+  Ok(())
+}
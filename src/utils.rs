@@ -7,4 +7,62 @@ pub(crate) fn choice_by_os<'a>(_: &'a str, on_windows: &'a str) -> &'a str {
 #[cfg(not(target_os = "windows"))]
 pub(crate) fn choice_by_os<'a>(on_linux: &'a str, _: &'a str) -> &'a str {
   on_linux
-}
\ No newline at end of file
+}
+
+/// Feeds the file to ``update`` by chunks
+fn read_chunks(path: &std::path::Path, mut update: impl FnMut(&[u8])) -> std::io::Result<()> {
+  use std::io::Read;
+
+  let mut file = std::fs::File::open(path)?;
+  let mut buffer = [0; 64 * 1024];
+
+  loop {
+    let read = file.read(&mut buffer)?;
+
+    if read == 0 {
+      return Ok(());
+    }
+
+    update(&buffer[..read]);
+  }
+}
+
+/// SHA1 of the file in hex
+pub(crate) fn file_sha1(path: &std::path::Path) -> std::io::Result<String> {
+  let mut hasher = sha1_smol::Sha1::new();
+  read_chunks(path, |chunk| hasher.update(chunk))?;
+
+  Ok(hasher.digest().to_string())
+}
+
+/// SHA256 of the file in hex
+pub(crate) fn file_sha256(path: &std::path::Path) -> std::io::Result<String> {
+  use sha2::Digest;
+
+  let mut hasher = sha2::Sha256::new();
+  read_chunks(path, |chunk| hasher.update(chunk))?;
+
+  Ok(hasher.finalize()
+    .iter()
+    .map(|byte| format!("{:02x}", byte))
+    .collect())
+}
+
+/// Collects every file in ``dir`` recursively
+pub(crate) fn walk_files(dir: &std::path::Path, files: &mut Vec<std::path::PathBuf>) -> std::io::Result<()> {
+  if !dir.is_dir() {
+    return Ok(());
+  }
+
+  for entry in std::fs::read_dir(dir)? {
+    let path = entry?.path();
+
+    if path.is_dir() {
+      walk_files(&path, files)?;
+    } else {
+      files.push(path);
+    }
+  }
+
+  Ok(())
+}